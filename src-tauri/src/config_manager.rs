@@ -1,14 +1,37 @@
 use std::fs;
 use std::env;
+use std::fmt;
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 use std::process::Command;
 use serde::{Deserialize, Serialize};
 use anyhow::{Result, Context};
+use crate::download_schedule::DownloadWindow;
+
+// Number of model pulls that run at the same time when not configured
+pub(crate) const DEFAULT_MAX_CONCURRENT_DOWNLOADS: usize = 2;
+
+// Host used when neither the config nor OLLAMA_HOST names one
+const DEFAULT_OLLAMA_HOST: &str = "http://127.0.0.1:11434";
+
+// Service the host credentials are stored under in the OS keychain, one entry per host
+const KEYCHAIN_SERVICE: &str = "ollama-pro";
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AppConfig {
     pub ollama_host: Option<String>,
     pub ollama_models_path: Option<String>,
+    pub max_concurrent_downloads: Option<usize>,
+    // Downloads only run inside this daily window when set
+    pub download_window: Option<DownloadWindow>,
+    // Profile whose server is used, the host and models path settings above apply when unset
+    #[serde(default)]
+    pub active_profile: Option<String>,
+    #[serde(default)]
+    pub server_profiles: BTreeMap<String, ServerProfile>,
+    // Hosts with credentials in the OS keychain, the secrets themselves never touch this file
+    #[serde(default)]
+    pub auth_hosts: Vec<String>,
 }
 
 impl Default for AppConfig {
@@ -16,13 +39,204 @@ impl Default for AppConfig {
         Self {
             ollama_host: None,
             ollama_models_path: None,
+            max_concurrent_downloads: None,
+            download_window: None,
+            active_profile: None,
+            server_profiles: BTreeMap::new(),
+            auth_hosts: Vec::new(),
+        }
+    }
+}
+
+/// TLS settings for an HTTPS server
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TlsOptions {
+    // PEM file with an extra root certificate, e.g. a company CA
+    #[serde(default)]
+    pub ca_cert_path: Option<String>,
+    // Skip certificate validation, only for self-signed test servers
+    #[serde(default)]
+    pub accept_invalid_certs: bool,
+}
+
+/// Request timeouts for a server, unset values keep the client defaults
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TimeoutOptions {
+    // Seconds for regular requests such as listing or showing models
+    #[serde(default)]
+    pub request_secs: Option<u64>,
+    // Seconds for streamed chats and generations, slow hardware may need more
+    #[serde(default)]
+    pub stream_secs: Option<u64>,
+}
+
+/// A named Ollama server
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ServerProfile {
+    pub host: String,
+    // Headers sent with every request; credential headers are moved to the host auth, which is stored in the keychain
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+    #[serde(default)]
+    pub tls: TlsOptions,
+    #[serde(default)]
+    pub timeouts: TimeoutOptions,
+    // Models directory of the server when it is reachable from this machine
+    #[serde(default)]
+    pub models_path: Option<String>,
+}
+
+/// Authorization scheme for a server behind an authenticating reverse proxy
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AuthScheme {
+    Bearer { token: String },
+    Basic { username: String, password: String },
+}
+
+impl AuthScheme {
+    pub fn name(&self) -> &'static str {
+        match self {
+            AuthScheme::Bearer { .. } => "bearer",
+            AuthScheme::Basic { .. } => "basic",
+        }
+    }
+}
+
+// Secrets must not end up in logs, so only the scheme and the user name are printed
+impl fmt::Debug for AuthScheme {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AuthScheme::Bearer { .. } => f.debug_struct("Bearer").finish_non_exhaustive(),
+            AuthScheme::Basic { username, .. } => f.debug_struct("Basic").field("username", username).finish_non_exhaustive(),
+        }
+    }
+}
+
+/// Credentials for one host, kept in the OS keychain (Keychain, Credential Manager or Secret Service)
+#[derive(Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct HostAuth {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scheme: Option<AuthScheme>,
+    // Custom headers such as an API key header, their values are treated as secrets too
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+}
+
+impl HostAuth {
+    pub fn is_empty(&self) -> bool {
+        self.scheme.is_none() && self.headers.is_empty()
+    }
+}
+
+impl fmt::Debug for HostAuth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HostAuth")
+            .field("scheme", &self.scheme)
+            .field("headers", &self.headers.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+/// What the frontend gets to see of a host's credentials, secrets are never sent back
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct HostAuthSummary {
+    pub host: String,
+    pub scheme: Option<&'static str>,
+    pub username: Option<String>,
+    pub header_names: Vec<String>,
+}
+
+/// Everything needed to talk to one server, resolved from a profile or the plain host settings
+#[derive(Clone, PartialEq)]
+pub struct ServerConnection {
+    pub profile: Option<String>,
+    pub host: String,
+    pub headers: BTreeMap<String, String>,
+    pub auth: HostAuth,
+    pub tls: TlsOptions,
+    pub timeouts: TimeoutOptions,
+    pub models_path: String,
+    // Whether models_path was configured rather than being the platform default or, for a profile, the host settings' path
+    pub models_path_configured: bool,
+}
+
+// Profile headers often carry tokens too, so only their names are printed
+impl fmt::Debug for ServerConnection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ServerConnection")
+            .field("profile", &self.profile)
+            .field("host", &self.host)
+            .field("headers", &self.headers.keys().collect::<Vec<_>>())
+            .field("auth", &self.auth)
+            .field("tls", &self.tls)
+            .field("timeouts", &self.timeouts)
+            .field("models_path", &self.models_path)
+            .field("models_path_configured", &self.models_path_configured)
+            .finish()
+    }
+}
+
+impl ServerConnection {
+    /// Whether the server runs on this machine, so its models directory describes what it serves
+    pub fn is_local(&self) -> bool {
+        let host = reqwest::Url::parse(&self.host).ok()
+            .and_then(|url| url.host_str().map(|host| host.trim_matches(|c| c == '[' || c == ']').to_string()));
+        match host.as_deref() {
+            Some("localhost") | Some("::1") | Some("0.0.0.0") => true,
+            Some(host) => host.starts_with("127."),
+            None => false,
+        }
+    }
+    
+    /// Models directory of the server as seen from this machine
+    ///
+    /// `None` for a remote server without a configured path, the local default says nothing about what it serves.
+    pub fn reachable_models_path(&self) -> Option<&str> {
+        (self.is_local() || self.models_path_configured).then_some(self.models_path.as_str())
+    }
+}
+
+/// What the frontend gets to see of a profile, header values stay in the backend
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ServerProfileSummary {
+    pub host: String,
+    pub header_names: Vec<String>,
+    pub tls: TlsOptions,
+    pub timeouts: TimeoutOptions,
+    pub models_path: Option<String>,
+}
+
+impl From<&ServerProfile> for ServerProfileSummary {
+    fn from(profile: &ServerProfile) -> Self {
+        Self {
+            host: profile.host.clone(),
+            header_names: profile.headers.keys().cloned().collect(),
+            tls: profile.tls.clone(),
+            timeouts: profile.timeouts.clone(),
+            models_path: profile.models_path.clone(),
         }
     }
 }
 
+/// Configured profiles and the active one
+#[derive(Debug, Serialize)]
+pub struct ServerProfiles {
+    pub active_profile: Option<String>,
+    pub profiles: BTreeMap<String, ServerProfileSummary>,
+}
+
+// Headers that carry credentials and belong in the keychain rather than in config.toml
+fn is_credential_header(name: &str) -> bool {
+    let name = name.trim().to_ascii_lowercase();
+    name == "authorization" || name == "proxy-authorization" || name.ends_with("-key") || name.ends_with("-token")
+}
+
 pub struct ConfigManager {
     config_path: PathBuf,
     config: AppConfig,
+    // Credentials read from the OS keychain, keyed by normalized host
+    credentials: BTreeMap<String, HostAuth>,
 }
 
 impl ConfigManager {
@@ -37,11 +251,35 @@ impl ConfigManager {
         
         let config_path = config_dir.join("config.toml");
         let config = Self::load_config(&config_path)?;
+        let credentials = Self::load_credentials(&config.auth_hosts);
         
-        Ok(Self {
+        let mut manager = Self {
             config_path,
             config,
-        })
+            credentials,
+        };
+        manager.migrate_profile_credentials();
+        Ok(manager)
+    }
+    
+    // Profiles saved by older versions may hold credential headers in plain text, move them to the keychain
+    fn migrate_profile_credentials(&mut self) {
+        let names: Vec<String> = self.config.server_profiles.iter()
+            .filter(|(_, profile)| profile.headers.keys().any(|name| is_credential_header(name)))
+            .map(|(name, _)| name.clone())
+            .collect();
+        for name in names {
+            let mut profile = self.config.server_profiles[&name].clone();
+            match self.store_profile_credentials(&mut profile) {
+                Ok(()) => {
+                    self.config.server_profiles.insert(name, profile);
+                    if let Err(e) = self.save_config() {
+                        eprintln!("Failed to save the config after moving credentials: {:#}", e);
+                    }
+                }
+                Err(e) => eprintln!("Failed to move the credential headers of profile {} to the keychain: {:#}", name, e),
+            }
+        }
     }
     
     fn load_config(config_path: &PathBuf) -> Result<AppConfig> {
@@ -64,9 +302,33 @@ impl ConfigManager {
         Ok(())
     }
     
+    // A host whose keychain entry can't be read is logged and left without credentials
+    fn load_credentials(hosts: &[String]) -> BTreeMap<String, HostAuth> {
+        let mut credentials = BTreeMap::new();
+        for host in hosts {
+            match read_keychain_auth(host) {
+                Ok(Some(auth)) => {
+                    credentials.insert(host.clone(), auth);
+                }
+                Ok(None) => eprintln!("No keychain entry for the credentials of {}", host),
+                Err(e) => eprintln!("Failed to read the credentials of {}: {:#}", host, e),
+            }
+        }
+        credentials
+    }
+    
     /// Get Ollama API address
-    /// Priority: User configuration > Environment variable > Default value
+    /// Priority: Active profile > User configuration > Environment variable > Default value
     pub fn get_ollama_host(&self) -> String {
+        match self.active_server_profile() {
+            Some((_, profile)) => self.normalize_host(&profile.host),
+            None => self.get_host_setting(),
+        }
+    }
+    
+    /// Address used when no profile is active
+    /// Priority: User configuration > Environment variable > Default value
+    fn get_host_setting(&self) -> String {
         // 1. Prioritize user-configured address
         if let Some(host) = &self.config.ollama_host {
             if !host.is_empty() {
@@ -82,7 +344,7 @@ impl ConfigManager {
         }
         
         // 3. Finally use default value
-        "http://127.0.0.1:11434".to_string()
+        DEFAULT_OLLAMA_HOST.to_string()
     }
     
     /// Set Ollama API address
@@ -141,13 +403,25 @@ impl ConfigManager {
     }
     
     /// Get Ollama models storage path
-    /// Priority: User configuration > Environment variable > Default value
+    /// Priority: Active profile > User configuration > Environment variable > Default value
     pub fn get_ollama_models_path(&self) -> String {
+        let profile_path = self.active_server_profile().and_then(|(_, profile)| profile.models_path.clone());
+        profile_path.filter(|path| !path.is_empty()).unwrap_or_else(|| self.get_models_path_setting())
+    }
+    
+    /// Models path used when no profile names one
+    /// Priority: User configuration > Environment variable > Default value
+    fn get_models_path_setting(&self) -> String {
+        self.configured_models_path().unwrap_or_else(|| self.get_default_models_path())
+    }
+    
+    // Models path from the user configuration or OLLAMA_MODELS, `None` when the platform default applies
+    fn configured_models_path(&self) -> Option<String> {
         // 1. Prioritize user-configured path
         if let Some(path) = &self.config.ollama_models_path {
             if !path.is_empty() {
                 // User configured path is the base .ollama directory
-                return path.clone();
+                return Some(path.clone());
             }
         }
         
@@ -155,16 +429,31 @@ impl ConfigManager {
         if let Ok(env_path) = env::var("OLLAMA_MODELS") {
             if !env_path.is_empty() {
                 // Environment variable points to base .ollama directory
-                return env_path;
+                return Some(env_path);
             }
         }
         
-        // 3. Finally use default value based on platform
-        self.get_default_models_path()
+        None
     }
     
     /// Set Ollama models storage path and update environment variable
+    ///
+    /// When the active profile has its own models path, that path is updated instead.
     pub fn set_ollama_models_path(&mut self, path: String) -> Result<()> {
+        if let Some(name) = self.active_profile_with_models_path() {
+            let normalized_path = if path.is_empty() {
+                None
+            } else {
+                let normalized = self.normalize_models_path(&path)?;
+                self.ensure_models_directory_exists(&normalized)?;
+                Some(normalized)
+            };
+            if let Some(profile) = self.config.server_profiles.get_mut(&name) {
+                profile.models_path = normalized_path;
+            }
+            return self.save_config();
+        }
+        
         let normalized_path = if path.is_empty() {
             None
         } else {
@@ -460,6 +749,244 @@ impl ConfigManager {
         Ok(())
     }
     
+    /// Get the maximum number of downloads the queue runs at once
+    pub fn get_max_concurrent_downloads(&self) -> usize {
+        self.config.max_concurrent_downloads
+            .filter(|max| *max > 0)
+            .unwrap_or(DEFAULT_MAX_CONCURRENT_DOWNLOADS)
+    }
+    
+    /// Set the maximum number of concurrent downloads, 0 restores the default
+    pub fn set_max_concurrent_downloads(&mut self, max: usize) -> Result<()> {
+        self.config.max_concurrent_downloads = if max == 0 { None } else { Some(max) };
+        self.save_config()
+    }
+    
+    /// Get the daily window in which downloads may run, `None` allows downloads at any time
+    pub fn get_download_window(&self) -> Option<DownloadWindow> {
+        self.config.download_window.clone()
+    }
+    
+    /// Set or clear the download window
+    pub fn set_download_window(&mut self, window: Option<DownloadWindow>) -> Result<()> {
+        self.config.download_window = window;
+        self.save_config()
+    }
+    
+    // The active profile, `None` when unset or when it names a deleted profile
+    fn active_server_profile(&self) -> Option<(&String, &ServerProfile)> {
+        let name = self.config.active_profile.as_ref()?;
+        self.config.server_profiles.get_key_value(name)
+    }
+    
+    fn active_profile_with_models_path(&self) -> Option<String> {
+        self.active_server_profile()
+            .filter(|(_, profile)| profile.models_path.as_ref().is_some_and(|path| !path.is_empty()))
+            .map(|(name, _)| name.clone())
+    }
+    
+    /// Get the configured server profiles and the active one
+    pub fn get_server_profiles(&self) -> ServerProfiles {
+        ServerProfiles {
+            active_profile: self.active_server_profile().map(|(name, _)| name.clone()),
+            profiles: self.config.server_profiles.iter()
+                .map(|(name, profile)| (name.clone(), ServerProfileSummary::from(profile)))
+                .collect(),
+        }
+    }
+    
+    // Move credential headers such as Authorization or X-Api-Key into the host's keychain entry
+    fn store_profile_credentials(&mut self, profile: &mut ServerProfile) -> Result<()> {
+        let (secrets, headers): (BTreeMap<_, _>, BTreeMap<_, _>) = std::mem::take(&mut profile.headers)
+            .into_iter()
+            .partition(|(name, _)| is_credential_header(name));
+        profile.headers = headers;
+        if secrets.is_empty() {
+            return Ok(());
+        }
+        
+        let mut auth = self.host_auth(&profile.host);
+        auth.headers.extend(secrets);
+        self.set_host_auth(&profile.host.clone(), auth)
+    }
+    
+    /// Add or replace a server profile
+    ///
+    /// Credential headers are stored with the host's credentials in the OS keychain instead of the profile.
+    pub fn save_server_profile(&mut self, name: String, mut profile: ServerProfile) -> Result<()> {
+        let name = name.trim().to_string();
+        if name.is_empty() {
+            return Err(anyhow::anyhow!("Profile name cannot be empty"));
+        }
+        if profile.host.trim().is_empty() {
+            return Err(anyhow::anyhow!("Profile {} needs a host", name));
+        }
+        profile.host = self.normalize_host(&profile.host);
+        profile.models_path = match profile.models_path.filter(|path| !path.trim().is_empty()) {
+            Some(path) => Some(self.normalize_models_path(&path)?),
+            None => None,
+        };
+        self.store_profile_credentials(&mut profile)?;
+        
+        self.config.server_profiles.insert(name, profile);
+        self.save_config()
+    }
+    
+    /// Delete a server profile, deleting the active profile falls back to the host settings
+    pub fn delete_server_profile(&mut self, name: &str) -> Result<bool> {
+        if self.config.server_profiles.remove(name).is_none() {
+            return Ok(false);
+        }
+        if self.config.active_profile.as_deref() == Some(name) {
+            self.config.active_profile = None;
+        }
+        self.save_config()?;
+        Ok(true)
+    }
+    
+    /// Switch to a profile, `None` goes back to the host settings
+    pub fn set_active_profile(&mut self, name: Option<String>) -> Result<()> {
+        if let Some(name) = &name {
+            if !self.config.server_profiles.contains_key(name) {
+                return Err(anyhow::anyhow!("Unknown server profile: {}", name));
+            }
+        }
+        self.config.active_profile = name;
+        self.save_config()
+    }
+    
+    /// Resolve the server to use: the named profile, else the active profile, else the host settings
+    pub fn resolve_server(&self, profile: Option<&str>) -> Result<ServerConnection> {
+        let selected = match profile.filter(|name| !name.is_empty()) {
+            Some(name) => Some(
+                self.config.server_profiles.get_key_value(name)
+                    .ok_or_else(|| anyhow::anyhow!("Unknown server profile: {}", name))?,
+            ),
+            None => self.active_server_profile(),
+        };
+        
+        Ok(match selected {
+            Some((name, profile)) => self.profile_server(name, profile),
+            None => self.host_settings_server(),
+        })
+    }
+    
+    /// Every configured server: the host settings and each profile, a host listed twice is kept once
+    pub fn resolve_all_servers(&self) -> Vec<ServerConnection> {
+        let mut servers: Vec<ServerConnection> = self.config.server_profiles.iter()
+            .map(|(name, profile)| self.profile_server(name, profile))
+            .collect();
+        let default_server = self.host_settings_server();
+        if !servers.iter().any(|server| server.host == default_server.host) {
+            servers.insert(0, default_server);
+        }
+        servers
+    }
+    
+    fn profile_server(&self, name: &str, profile: &ServerProfile) -> ServerConnection {
+        let host = self.normalize_host(&profile.host);
+        let profile_models_path = profile.models_path.clone().filter(|path| !path.is_empty());
+        ServerConnection {
+            profile: Some(name.to_string()),
+            auth: self.host_auth(&host),
+            host,
+            headers: profile.headers.clone(),
+            tls: profile.tls.clone(),
+            timeouts: profile.timeouts.clone(),
+            models_path_configured: profile_models_path.is_some(),
+            models_path: profile_models_path.unwrap_or_else(|| self.get_models_path_setting()),
+        }
+    }
+    
+    // Server from the plain host and models path settings
+    fn host_settings_server(&self) -> ServerConnection {
+        self.host_server(&self.get_host_setting())
+    }
+    
+    /// Server for an arbitrary host, e.g. one that is being validated before it is saved
+    pub fn host_server(&self, host: &str) -> ServerConnection {
+        let host = self.normalize_host(host);
+        ServerConnection {
+            profile: None,
+            auth: self.host_auth(&host),
+            host,
+            headers: BTreeMap::new(),
+            tls: TlsOptions::default(),
+            timeouts: TimeoutOptions::default(),
+            models_path: self.get_models_path_setting(),
+            models_path_configured: self.configured_models_path().is_some(),
+        }
+    }
+    
+    // Credentials are keyed by the normalized host without a trailing slash
+    fn auth_key(&self, host: &str) -> String {
+        self.normalize_host(host).trim_end_matches('/').to_string()
+    }
+    
+    fn host_auth(&self, host: &str) -> HostAuth {
+        self.credentials.get(&self.auth_key(host)).cloned().unwrap_or_default()
+    }
+    
+    /// Hosts with stored credentials, without the secrets
+    pub fn get_host_auth(&self) -> Vec<HostAuthSummary> {
+        self.credentials.iter()
+            .map(|(host, auth)| HostAuthSummary {
+                host: host.clone(),
+                scheme: auth.scheme.as_ref().map(AuthScheme::name),
+                username: match &auth.scheme {
+                    Some(AuthScheme::Basic { username, .. }) => Some(username.clone()),
+                    _ => None,
+                },
+                header_names: auth.headers.keys().cloned().collect(),
+            })
+            .collect()
+    }
+    
+    /// Store the credentials for a host, empty credentials remove the entry
+    pub fn set_host_auth(&mut self, host: &str, auth: HostAuth) -> Result<()> {
+        if host.trim().is_empty() {
+            return Err(anyhow::anyhow!("Host cannot be empty"));
+        }
+        match &auth.scheme {
+            Some(AuthScheme::Bearer { token }) if token.trim().is_empty() => {
+                return Err(anyhow::anyhow!("Bearer token cannot be empty"));
+            }
+            Some(AuthScheme::Basic { username, .. }) if username.trim().is_empty() || username.contains(':') => {
+                return Err(anyhow::anyhow!("Basic auth needs a user name without ':'"));
+            }
+            _ => {}
+        }
+        if auth.headers.keys().any(|name| name.trim().is_empty()) {
+            return Err(anyhow::anyhow!("Header names cannot be empty"));
+        }
+        
+        if auth.is_empty() {
+            self.clear_host_auth(host)?;
+            return Ok(());
+        }
+        
+        let key = self.auth_key(host);
+        write_keychain_auth(&key, &auth)?;
+        self.credentials.insert(key.clone(), auth);
+        if !self.config.auth_hosts.contains(&key) {
+            self.config.auth_hosts.push(key);
+            self.config.auth_hosts.sort();
+        }
+        self.save_config()
+    }
+    
+    /// Remove the credentials for a host
+    pub fn clear_host_auth(&mut self, host: &str) -> Result<bool> {
+        let key = self.auth_key(host);
+        if self.credentials.remove(&key).is_none() && !self.config.auth_hosts.contains(&key) {
+            return Ok(false);
+        }
+        delete_keychain_auth(&key)?;
+        self.config.auth_hosts.retain(|auth_host| *auth_host != key);
+        self.save_config()?;
+        Ok(true)
+    }
+    
     /// Get current configuration information (for debugging)
     pub fn get_config_info(&self) -> ConfigInfo {
         ConfigInfo {
@@ -470,10 +997,36 @@ impl ConfigManager {
             user_configured_models_path: self.config.ollama_models_path.clone(),
             env_models_path: env::var("OLLAMA_MODELS").ok(),
             effective_models_path: self.get_ollama_models_path(),
+            active_profile: self.active_server_profile().map(|(name, _)| name.clone()),
         }
     }
 }
 
+fn keychain_entry(host: &str) -> Result<keyring::Entry> {
+    keyring::Entry::new(KEYCHAIN_SERVICE, host).context("Failed to open the OS keychain")
+}
+
+fn read_keychain_auth(host: &str) -> Result<Option<HostAuth>> {
+    match keychain_entry(host)?.get_password() {
+        Ok(secret) => Ok(Some(serde_json::from_str(&secret).context("Invalid credentials in the OS keychain")?)),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(e).context("Failed to read from the OS keychain"),
+    }
+}
+
+fn write_keychain_auth(host: &str, auth: &HostAuth) -> Result<()> {
+    let secret = serde_json::to_string(auth).context("Failed to serialize credentials")?;
+    keychain_entry(host)?.set_password(&secret)
+        .context("Failed to store credentials in the OS keychain")
+}
+
+fn delete_keychain_auth(host: &str) -> Result<()> {
+    match keychain_entry(host)?.delete_password() {
+        Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+        Err(e) => Err(e).context("Failed to delete credentials from the OS keychain"),
+    }
+}
+
 #[derive(Debug, Serialize)]
 pub struct ConfigInfo {
     pub config_path: String,
@@ -483,6 +1036,7 @@ pub struct ConfigInfo {
     pub user_configured_models_path: Option<String>,
     pub env_models_path: Option<String>,
     pub effective_models_path: String,
+    pub active_profile: Option<String>,
 }
 
 // Global configuration manager instance
@@ -500,6 +1054,7 @@ pub fn get_config_manager() -> Result<&'static Mutex<ConfigManager>, String> {
                 Mutex::new(ConfigManager {
                     config_path: std::path::PathBuf::from("config.toml"),
                     config: AppConfig::default(),
+                    credentials: BTreeMap::new(),
                 })
             }
         }
@@ -568,6 +1123,138 @@ pub fn clear_ollama_models_path() -> Result<String, String> {
     Ok(manager.get_ollama_models_path())
 }
 
+/// Tauri command: Get the maximum number of concurrent downloads
+#[tauri::command]
+pub fn get_max_concurrent_downloads() -> Result<usize, String> {
+    let manager = get_config_manager().map_err(|e| e.to_string())?;
+    let manager = manager.lock().map_err(|e| e.to_string())?;
+    Ok(manager.get_max_concurrent_downloads())
+}
+
+/// Tauri command: Set the maximum number of concurrent downloads
+#[tauri::command]
+pub fn set_max_concurrent_downloads(max: usize, app_handle: tauri::AppHandle) -> Result<usize, String> {
+    let effective_max = {
+        let manager = get_config_manager().map_err(|e| e.to_string())?;
+        let mut manager = manager.lock().map_err(|e| e.to_string())?;
+        manager.set_max_concurrent_downloads(max).map_err(|e| e.to_string())?;
+        manager.get_max_concurrent_downloads()
+    };
+    
+    // A higher limit lets queued downloads start right away
+    crate::ollama_api::start_queued_downloads(&app_handle);
+    Ok(effective_max)
+}
+
+/// Tauri command: Get the download window
+#[tauri::command]
+pub fn get_download_window() -> Result<Option<DownloadWindow>, String> {
+    let manager = get_config_manager().map_err(|e| e.to_string())?;
+    let manager = manager.lock().map_err(|e| e.to_string())?;
+    Ok(manager.get_download_window())
+}
+
+/// Tauri command: Set the download window (HH:MM local time), omitting either time clears it
+#[tauri::command]
+pub fn set_download_window(start: Option<String>, end: Option<String>, app_handle: tauri::AppHandle) -> Result<Option<DownloadWindow>, String> {
+    let window = match (start, end) {
+        (Some(start), Some(end)) => Some(DownloadWindow::new(&start, &end)?),
+        _ => None,
+    };
+    
+    {
+        let manager = get_config_manager().map_err(|e| e.to_string())?;
+        let mut manager = manager.lock().map_err(|e| e.to_string())?;
+        manager.set_download_window(window.clone()).map_err(|e| e.to_string())?;
+    }
+    
+    // Pause or start downloads right away instead of at the next scheduler tick
+    crate::ollama_api::apply_download_window(&app_handle);
+    Ok(window)
+}
+
+/// Resolve the server for a command, `profile` overrides the active profile
+pub fn resolve_server(profile: Option<&str>) -> Result<ServerConnection, String> {
+    let manager = get_config_manager().map_err(|e| e.to_string())?;
+    let manager = manager.lock().map_err(|e| e.to_string())?;
+    manager.resolve_server(profile).map_err(|e| e.to_string())
+}
+
+/// All configured servers, see `ConfigManager::resolve_all_servers`
+pub fn resolve_all_servers() -> Result<Vec<ServerConnection>, String> {
+    let manager = get_config_manager().map_err(|e| e.to_string())?;
+    let manager = manager.lock().map_err(|e| e.to_string())?;
+    Ok(manager.resolve_all_servers())
+}
+
+/// Server for an arbitrary host with its stored credentials
+pub fn host_server(host: &str) -> Result<ServerConnection, String> {
+    let manager = get_config_manager().map_err(|e| e.to_string())?;
+    let manager = manager.lock().map_err(|e| e.to_string())?;
+    Ok(manager.host_server(host))
+}
+
+/// Tauri command: List the hosts with stored credentials, secrets are left out
+#[tauri::command]
+pub fn get_host_auth() -> Result<Vec<HostAuthSummary>, String> {
+    let manager = get_config_manager().map_err(|e| e.to_string())?;
+    let manager = manager.lock().map_err(|e| e.to_string())?;
+    Ok(manager.get_host_auth())
+}
+
+/// Tauri command: Store bearer, basic or header credentials for a host
+#[tauri::command]
+pub fn set_host_auth(host: String, auth: HostAuth) -> Result<Vec<HostAuthSummary>, String> {
+    let manager = get_config_manager().map_err(|e| e.to_string())?;
+    let mut manager = manager.lock().map_err(|e| e.to_string())?;
+    manager.set_host_auth(&host, auth).map_err(|e| e.to_string())?;
+    Ok(manager.get_host_auth())
+}
+
+/// Tauri command: Remove the credentials for a host
+#[tauri::command]
+pub fn clear_host_auth(host: String) -> Result<Vec<HostAuthSummary>, String> {
+    let manager = get_config_manager().map_err(|e| e.to_string())?;
+    let mut manager = manager.lock().map_err(|e| e.to_string())?;
+    manager.clear_host_auth(&host).map_err(|e| e.to_string())?;
+    Ok(manager.get_host_auth())
+}
+
+/// Tauri command: Get the server profiles and the active one
+#[tauri::command]
+pub fn get_server_profiles() -> Result<ServerProfiles, String> {
+    let manager = get_config_manager().map_err(|e| e.to_string())?;
+    let manager = manager.lock().map_err(|e| e.to_string())?;
+    Ok(manager.get_server_profiles())
+}
+
+/// Tauri command: Add or replace a server profile
+#[tauri::command]
+pub fn save_server_profile(name: String, profile: ServerProfile) -> Result<ServerProfiles, String> {
+    let manager = get_config_manager().map_err(|e| e.to_string())?;
+    let mut manager = manager.lock().map_err(|e| e.to_string())?;
+    manager.save_server_profile(name, profile).map_err(|e| e.to_string())?;
+    Ok(manager.get_server_profiles())
+}
+
+/// Tauri command: Delete a server profile
+#[tauri::command]
+pub fn delete_server_profile(name: String) -> Result<ServerProfiles, String> {
+    let manager = get_config_manager().map_err(|e| e.to_string())?;
+    let mut manager = manager.lock().map_err(|e| e.to_string())?;
+    manager.delete_server_profile(&name).map_err(|e| e.to_string())?;
+    Ok(manager.get_server_profiles())
+}
+
+/// Tauri command: Switch the active server profile, omitting the name goes back to the host settings
+#[tauri::command]
+pub fn set_active_profile(name: Option<String>) -> Result<ServerProfiles, String> {
+    let manager = get_config_manager().map_err(|e| e.to_string())?;
+    let mut manager = manager.lock().map_err(|e| e.to_string())?;
+    manager.set_active_profile(name).map_err(|e| e.to_string())?;
+    Ok(manager.get_server_profiles())
+}
+
 /// Tauri command: Restart Ollama service with new environment variables
 #[tauri::command]
 pub fn restart_ollama_service() -> Result<String, String> {
@@ -648,6 +1335,7 @@ mod tests {
         let manager = ConfigManager {
             config_path: PathBuf::new(),
             config: AppConfig::default(),
+            credentials: BTreeMap::new(),
         };
         
         // Test complete URL
@@ -690,6 +1378,7 @@ mod tests {
         let manager = ConfigManager {
             config_path: PathBuf::new(),
             config: AppConfig::default(),
+            credentials: BTreeMap::new(),
         };
         
         // Test cases that should result in proper environment variable values
@@ -711,4 +1400,154 @@ mod tests {
             assert_eq!(env_host, expected_env, "Environment variable extraction failed for: {}", input);
         }
     }
-}
\ No newline at end of file
+    
+    #[test]
+    fn test_server_profiles() {
+        keyring::set_default_credential_builder(keyring::mock::default_credential_builder());
+        let mut manager = ConfigManager {
+            config_path: env::temp_dir().join(format!("ollama-pro-profiles-{}.toml", std::process::id())),
+            config: AppConfig::default(),
+            credentials: BTreeMap::new(),
+        };
+        manager.config.ollama_host = Some("http://127.0.0.1:11434".to_string());
+        
+        let mut headers = BTreeMap::new();
+        headers.insert("Authorization".to_string(), "Bearer secret".to_string());
+        headers.insert("X-Tenant".to_string(), "research".to_string());
+        manager.save_server_profile("gpu".to_string(), ServerProfile {
+            host: "10.0.0.5".to_string(),
+            headers,
+            tls: TlsOptions::default(),
+            timeouts: TimeoutOptions { request_secs: Some(120), stream_secs: None },
+            models_path: Some("/srv/ollama/".to_string()),
+        }).unwrap();
+        manager.save_server_profile("staging".to_string(), ServerProfile {
+            host: "https://staging.example.com".to_string(),
+            ..Default::default()
+        }).unwrap();
+        assert!(manager.save_server_profile(" ".to_string(), ServerProfile::default()).is_err());
+        
+        // Without an active profile the host settings apply
+        let server = manager.resolve_server(None).unwrap();
+        assert!(server.is_local());
+        assert_eq!((server.profile, server.host.as_str()), (None, "http://127.0.0.1:11434"));
+        
+        manager.set_active_profile(Some("gpu".to_string())).unwrap();
+        assert_eq!(manager.get_ollama_host(), "http://10.0.0.5:11434");
+        assert_eq!(manager.get_ollama_models_path(), "/srv/ollama");
+        let server = manager.resolve_server(None).unwrap();
+        // Credential headers go to the host's keychain entry, other headers stay with the profile
+        assert_eq!(server.headers.keys().collect::<Vec<_>>(), vec!["X-Tenant"]);
+        assert_eq!(server.auth.headers.get("Authorization").map(String::as_str), Some("Bearer secret"));
+        assert!(!fs::read_to_string(&manager.config_path).unwrap().contains("Bearer secret"));
+        let profiles = manager.get_server_profiles();
+        assert_eq!(profiles.profiles["gpu"].header_names, vec!["X-Tenant"]);
+        assert!(!serde_json::to_string(&profiles).unwrap().contains("research"));
+        assert_eq!((server.timeouts.request_secs, server.timeouts.stream_secs), (Some(120), None));
+        assert!(server.models_path_configured);
+        
+        // An override wins over the active profile, unknown names are errors
+        let server = manager.resolve_server(Some("staging")).unwrap();
+        assert!(!server.is_local());
+        assert_eq!((server.profile.as_deref(), server.host.as_str()), (Some("staging"), "https://staging.example.com"));
+        // Without its own models path a remote profile has no directory this machine can read
+        assert!(!server.models_path_configured);
+        assert_eq!(server.reachable_models_path(), None);
+        assert!(manager.resolve_server(Some("laptop")).is_err());
+        assert!(manager.set_active_profile(Some("laptop".to_string())).is_err());
+        
+        // The host settings join the profiles unless a profile already uses that host
+        let hosts: Vec<String> = manager.resolve_all_servers().into_iter().map(|server| server.host).collect();
+        assert_eq!(hosts, vec!["http://127.0.0.1:11434", "http://10.0.0.5:11434", "https://staging.example.com"]);
+        
+        // Profiles survive a save and load
+        let loaded = ConfigManager::load_config(&manager.config_path).unwrap();
+        assert_eq!(loaded.server_profiles, manager.config.server_profiles);
+        assert_eq!(loaded.active_profile.as_deref(), Some("gpu"));
+        
+        // Credential headers saved by older versions are moved on startup
+        let mut legacy_headers = BTreeMap::new();
+        legacy_headers.insert("x-api-key".to_string(), "k3y".to_string());
+        manager.config.server_profiles.insert("legacy".to_string(), ServerProfile {
+            host: "https://legacy.example.com".to_string(),
+            headers: legacy_headers,
+            ..Default::default()
+        });
+        manager.migrate_profile_credentials();
+        assert!(manager.config.server_profiles["legacy"].headers.is_empty());
+        let server = manager.resolve_server(Some("legacy")).unwrap();
+        assert_eq!(server.auth.headers.get("x-api-key").map(String::as_str), Some("k3y"));
+        assert!(is_credential_header("Proxy-Authorization") && is_credential_header("X-Auth-Token"));
+        assert!(!is_credential_header("X-Tenant"));
+        
+        // Deleting the active profile falls back to the host settings
+        assert!(manager.delete_server_profile("gpu").unwrap());
+        assert_eq!(manager.get_ollama_host(), "http://127.0.0.1:11434");
+        
+        let _ = fs::remove_file(&manager.config_path);
+    }
+    
+    #[test]
+    fn test_host_auth() {
+        // The mock keychain keeps nothing between entries, the manager's own copy serves the requests
+        keyring::set_default_credential_builder(keyring::mock::default_credential_builder());
+        let mut manager = ConfigManager {
+            config_path: env::temp_dir().join(format!("ollama-pro-auth-{}.toml", std::process::id())),
+            config: AppConfig::default(),
+            credentials: BTreeMap::new(),
+        };
+        manager.config.ollama_host = Some("https://ollama.example.com".to_string());
+        
+        manager.set_host_auth("https://ollama.example.com/", HostAuth {
+            scheme: Some(AuthScheme::Bearer { token: "s3cret".to_string() }),
+            headers: BTreeMap::new(),
+        }).unwrap();
+        let mut headers = BTreeMap::new();
+        headers.insert("X-Api-Key".to_string(), "k3y".to_string());
+        manager.set_host_auth("10.0.0.5", HostAuth {
+            scheme: Some(AuthScheme::Basic { username: "ops".to_string(), password: "hunter2".to_string() }),
+            headers,
+        }).unwrap();
+        assert!(manager.set_host_auth("10.0.0.6", HostAuth {
+            scheme: Some(AuthScheme::Bearer { token: " ".to_string() }),
+            headers: BTreeMap::new(),
+        }).is_err());
+        
+        // Credentials follow the host, whether it comes from a profile or the host settings
+        let server = manager.resolve_server(None).unwrap();
+        assert_eq!(server.auth.scheme, Some(AuthScheme::Bearer { token: "s3cret".to_string() }));
+        let mut profile_headers = BTreeMap::new();
+        profile_headers.insert("X-Tenant".to_string(), "t3nant".to_string());
+        manager.save_server_profile("gpu".to_string(), ServerProfile {
+            host: "10.0.0.5:11434".to_string(),
+            headers: profile_headers,
+            ..Default::default()
+        }).unwrap();
+        let server = manager.resolve_server(Some("gpu")).unwrap();
+        assert_eq!(server.auth.scheme.as_ref().map(AuthScheme::name), Some("basic"));
+        assert!(manager.host_server("http://10.0.0.6:11434").auth.is_empty());
+        
+        // Neither the summaries nor debug output carry secrets
+        let summaries = manager.get_host_auth();
+        assert_eq!(summaries[0].host, "http://10.0.0.5:11434");
+        assert_eq!(summaries[0].username.as_deref(), Some("ops"));
+        assert_eq!(summaries[0].header_names, vec!["X-Api-Key"]);
+        assert_eq!(summaries[1].scheme, Some("bearer"));
+        let printed = format!("{:?} {:?}", summaries, server);
+        assert!(!printed.contains("hunter2") && !printed.contains("k3y") && !printed.contains("s3cret"));
+        assert!(printed.contains("X-Tenant") && !printed.contains("t3nant"));
+        
+        // config.toml only lists which hosts have credentials
+        let loaded = ConfigManager::load_config(&manager.config_path).unwrap();
+        assert_eq!(loaded.auth_hosts, vec!["http://10.0.0.5:11434", "https://ollama.example.com"]);
+        let content = fs::read_to_string(&manager.config_path).unwrap();
+        assert!(!content.contains("hunter2") && !content.contains("s3cret"));
+        
+        assert!(manager.clear_host_auth("10.0.0.5").unwrap());
+        assert!(!manager.clear_host_auth("10.0.0.5").unwrap());
+        assert_eq!(manager.get_host_auth().len(), 1);
+        assert_eq!(manager.config.auth_hosts, vec!["https://ollama.example.com"]);
+        
+        let _ = fs::remove_file(&manager.config_path);
+    }
+}
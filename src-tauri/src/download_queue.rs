@@ -0,0 +1,233 @@
+use serde::{Deserialize, Serialize};
+
+/// Lifecycle state of a queued download
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum DownloadStatus {
+    /// Waiting for a free download slot
+    Queued,
+    /// Currently pulling
+    Active,
+    /// Stopped by the user, progress is kept for a later resume
+    #[default]
+    Paused,
+    /// The pull ended with an error
+    Failed,
+    /// The pull finished successfully
+    Completed,
+}
+
+/// A pull waiting in (or running from) the download queue
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct QueueEntry {
+    pub channel_id: String,
+    pub model_name: String,
+    /// Higher priorities start first, equal priorities keep their enqueue order
+    pub priority: i32,
+    /// Server profile to pull from, `None` for the active server
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub profile: Option<String>,
+    pub status: DownloadStatus,
+    pub enqueued_at: u64,
+    /// Incremented each time the entry is started, tells a finished run apart from a restarted one
+    #[serde(skip)]
+    pub run: u64,
+}
+
+/// Ordered download queue
+///
+/// Active and queued entries share one list; the order of the queued entries
+/// is the order in which they get a download slot.
+#[derive(Debug, Default)]
+pub struct DownloadQueue {
+    entries: Vec<QueueEntry>,
+    runs: u64,
+}
+
+impl DownloadQueue {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn contains(&self, channel_id: &str) -> bool {
+        self.entries.iter().any(|e| e.channel_id == channel_id)
+    }
+
+    pub fn get(&self, channel_id: &str) -> Option<&QueueEntry> {
+        self.entries.iter().find(|e| e.channel_id == channel_id)
+    }
+
+    /// Current queue contents, active entries first, then queued entries in start order
+    pub fn snapshot(&self) -> Vec<QueueEntry> {
+        let (mut active, queued): (Vec<_>, Vec<_>) = self.entries
+            .iter()
+            .cloned()
+            .partition(|e| e.status == DownloadStatus::Active);
+        active.extend(queued);
+        active
+    }
+
+    pub fn active_count(&self) -> usize {
+        self.entries.iter().filter(|e| e.status == DownloadStatus::Active).count()
+    }
+
+    /// Insert a queued entry behind every queued entry of the same or higher priority
+    pub fn enqueue(&mut self, mut entry: QueueEntry) {
+        entry.status = DownloadStatus::Queued;
+        let index = self.entries
+            .iter()
+            .position(|e| e.status == DownloadStatus::Queued && e.priority < entry.priority)
+            .unwrap_or(self.entries.len());
+        self.entries.insert(index, entry);
+    }
+
+    /// Append an entry as is, used to restore a persisted queue in its saved order
+    pub fn push_back(&mut self, entry: QueueEntry) {
+        self.entries.push(entry);
+    }
+
+    pub fn remove(&mut self, channel_id: &str) -> Option<QueueEntry> {
+        let index = self.entries.iter().position(|e| e.channel_id == channel_id)?;
+        Some(self.entries.remove(index))
+    }
+
+    /// Mark queued entries active until `max_active` downloads run, returns the started entries
+    pub fn start_next(&mut self, max_active: usize) -> Vec<QueueEntry> {
+        let mut free_slots = max_active.saturating_sub(self.active_count());
+        let mut started = Vec::new();
+
+        for entry in self.entries.iter_mut() {
+            if free_slots == 0 {
+                break;
+            }
+            if entry.status == DownloadStatus::Queued {
+                self.runs += 1;
+                entry.status = DownloadStatus::Active;
+                entry.run = self.runs;
+                started.push(entry.clone());
+                free_slots -= 1;
+            }
+        }
+
+        started
+    }
+
+    /// Put an active entry back to queued without changing its place in the queue
+    pub fn defer(&mut self, channel_id: &str) -> bool {
+        match self.entries.iter_mut().find(|e| e.channel_id == channel_id) {
+            Some(entry) if entry.status == DownloadStatus::Active => {
+                entry.status = DownloadStatus::Queued;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Move a queued entry to `position` among the queued entries (0 starts next)
+    pub fn move_to(&mut self, channel_id: &str, position: usize) -> bool {
+        match self.get(channel_id) {
+            Some(entry) if entry.status == DownloadStatus::Queued => {}
+            _ => return false,
+        }
+
+        let entry = self.remove(channel_id).unwrap();
+        let index = self.entries
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| e.status == DownloadStatus::Queued)
+            .map(|(i, _)| i)
+            .nth(position)
+            .unwrap_or(self.entries.len());
+        self.entries.insert(index, entry);
+        true
+    }
+
+    /// Change the priority of a queued entry and re-sort it among the queued entries
+    pub fn set_priority(&mut self, channel_id: &str, priority: i32) -> bool {
+        match self.get(channel_id) {
+            Some(entry) if entry.status == DownloadStatus::Queued => {}
+            _ => return false,
+        }
+
+        let mut entry = self.remove(channel_id).unwrap();
+        entry.priority = priority;
+        self.enqueue(entry);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(id: &str, priority: i32) -> QueueEntry {
+        QueueEntry {
+            channel_id: id.to_string(),
+            model_name: format!("model-{}", id),
+            priority,
+            profile: None,
+            status: DownloadStatus::Queued,
+            enqueued_at: 0,
+            run: 0,
+        }
+    }
+
+    fn ids(queue: &DownloadQueue) -> Vec<String> {
+        queue.snapshot().into_iter().map(|e| e.channel_id).collect()
+    }
+
+    #[test]
+    fn test_priority_order_and_concurrency_limit() {
+        let mut queue = DownloadQueue::new();
+        queue.enqueue(entry("a", 0));
+        queue.enqueue(entry("b", 5));
+        queue.enqueue(entry("c", 0));
+        queue.enqueue(entry("d", 5));
+        assert_eq!(ids(&queue), vec!["b", "d", "a", "c"]);
+
+        let started: Vec<String> = queue.start_next(2).into_iter().map(|e| e.channel_id).collect();
+        assert_eq!(started, vec!["b", "d"]);
+        // No free slot until an active download finishes
+        assert!(queue.start_next(2).is_empty());
+
+        queue.remove("b");
+        let started: Vec<String> = queue.start_next(2).into_iter().map(|e| e.channel_id).collect();
+        assert_eq!(started, vec!["a"]);
+    }
+
+    #[test]
+    fn test_reorder_only_moves_queued_entries() {
+        let mut queue = DownloadQueue::new();
+        for id in ["a", "b", "c", "d"] {
+            queue.enqueue(entry(id, 0));
+        }
+        queue.start_next(1);
+
+        assert!(queue.move_to("d", 0));
+        assert_eq!(ids(&queue), vec!["a", "d", "b", "c"]);
+        assert!(queue.move_to("d", 10));
+        assert_eq!(ids(&queue), vec!["a", "b", "c", "d"]);
+
+        // Active downloads can't be reordered
+        assert!(!queue.move_to("a", 2));
+
+        assert!(queue.set_priority("c", 1));
+        assert_eq!(ids(&queue), vec!["a", "c", "b", "d"]);
+    }
+
+    #[test]
+    fn test_deferred_entry_keeps_its_place_and_gets_a_new_run() {
+        let mut queue = DownloadQueue::new();
+        queue.enqueue(entry("a", 0));
+        queue.enqueue(entry("b", 0));
+        let first_run = queue.start_next(1)[0].run;
+
+        assert!(queue.defer("a"));
+        assert!(!queue.defer("b"));
+        assert_eq!(queue.active_count(), 0);
+
+        let restarted = queue.start_next(1);
+        assert_eq!(restarted[0].channel_id, "a");
+        assert_ne!(restarted[0].run, first_run);
+    }
+}
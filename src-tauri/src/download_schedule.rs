@@ -0,0 +1,117 @@
+use chrono::{Local, Timelike};
+use serde::{Deserialize, Serialize};
+
+const MINUTES_PER_DAY: u32 = 24 * 60;
+
+/// Daily local-time window in which queued downloads may run
+///
+/// `start` and `end` are `HH:MM`. A window whose end is before its start
+/// spans midnight (e.g. 22:00-07:00); equal times allow the whole day.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DownloadWindow {
+    pub start: String,
+    pub end: String,
+}
+
+impl DownloadWindow {
+    /// Validate both times and normalize them to `HH:MM`
+    pub fn new(start: &str, end: &str) -> Result<Self, String> {
+        let start = parse_time(start)?;
+        let end = parse_time(end)?;
+        Ok(Self {
+            start: format_time(start),
+            end: format_time(end),
+        })
+    }
+
+    fn bounds(&self) -> Result<(u32, u32), String> {
+        Ok((parse_time(&self.start)?, parse_time(&self.end)?))
+    }
+
+    /// Whether downloads may run at `minute` (minutes since local midnight)
+    /// An unparsable window (hand-edited config) never blocks downloads
+    pub fn is_open_at(&self, minute: u32) -> bool {
+        let Ok((start, end)) = self.bounds() else {
+            return true;
+        };
+        if start == end {
+            true
+        } else if start < end {
+            minute >= start && minute < end
+        } else {
+            minute >= start || minute < end
+        }
+    }
+
+    /// Minutes from `minute` until the window opens, 0 while it is open
+    pub fn minutes_until_open(&self, minute: u32) -> u32 {
+        if self.is_open_at(minute) {
+            return 0;
+        }
+        let start = self.bounds().map(|(start, _)| start).unwrap_or(minute);
+        (start + MINUTES_PER_DAY - minute) % MINUTES_PER_DAY
+    }
+
+    /// Seconds until the window opens at the current local time, `None` while it is open
+    pub fn seconds_until_open_now(&self) -> Option<u64> {
+        let now = Local::now();
+        let minute = now.hour() * 60 + now.minute();
+        match self.minutes_until_open(minute) {
+            0 => None,
+            minutes => Some(minutes as u64 * 60 - now.second() as u64),
+        }
+    }
+}
+
+// Parse `HH:MM` (or `H:MM`) into minutes since midnight
+fn parse_time(value: &str) -> Result<u32, String> {
+    let invalid = || format!("Invalid time {:?}, expected HH:MM", value);
+    let (hours, minutes) = value.trim().split_once(':').ok_or_else(invalid)?;
+    let hours: u32 = hours.parse().map_err(|_| invalid())?;
+    let minutes: u32 = minutes.parse().map_err(|_| invalid())?;
+    if hours > 23 || minutes > 59 {
+        return Err(invalid());
+    }
+    Ok(hours * 60 + minutes)
+}
+
+fn format_time(minutes: u32) -> String {
+    format!("{:02}:{:02}", minutes / 60, minutes % 60)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(time: &str) -> u32 {
+        parse_time(time).unwrap()
+    }
+
+    #[test]
+    fn test_window_across_midnight() {
+        let window = DownloadWindow::new("22:00", "7:00").unwrap();
+        assert_eq!(window.end, "07:00");
+
+        assert!(window.is_open_at(at("23:30")));
+        assert!(window.is_open_at(at("00:00")));
+        assert!(window.is_open_at(at("06:59")));
+        assert!(!window.is_open_at(at("07:00")));
+        assert!(!window.is_open_at(at("12:00")));
+
+        assert_eq!(window.minutes_until_open(at("21:30")), 30);
+        assert_eq!(window.minutes_until_open(at("07:00")), 15 * 60);
+        assert_eq!(window.minutes_until_open(at("23:00")), 0);
+    }
+
+    #[test]
+    fn test_same_day_window_and_validation() {
+        let window = DownloadWindow::new("09:00", "17:30").unwrap();
+        assert!(window.is_open_at(at("09:00")));
+        assert!(!window.is_open_at(at("17:30")));
+        assert_eq!(window.minutes_until_open(at("18:00")), 15 * 60);
+
+        assert!(DownloadWindow::new("09:00", "09:00").unwrap().is_open_at(at("03:00")));
+        assert!(DownloadWindow::new("24:00", "07:00").is_err());
+        assert!(DownloadWindow::new("22", "07:00").is_err());
+    }
+}
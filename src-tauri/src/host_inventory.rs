@@ -0,0 +1,238 @@
+use crate::config_manager::{resolve_all_servers, ServerConnection};
+use crate::ollama_api::{OllamaModel, OllamaRunningModel};
+use crate::ollama_client::OllamaClient;
+use crate::ollama_error::OllamaError;
+use futures_util::future::join_all;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+
+// Time each host gets to answer both listings when the caller sets none
+const DEFAULT_HOST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Reachability of one host, `error` is set when it could not be listed
+#[derive(Debug, Clone, Serialize)]
+pub struct HostStatus {
+    pub profile: Option<String>,
+    pub host: String,
+    pub reachable: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub elapsed_ms: u64,
+    pub model_count: usize,
+    pub running_count: usize,
+}
+
+/// A model as installed on one host
+#[derive(Debug, Clone, Serialize)]
+pub struct ModelPlacement {
+    pub profile: Option<String>,
+    pub host: String,
+    pub digest: String,
+    pub size: i64,
+    pub modified_at: String,
+    pub running: bool,
+}
+
+/// A model name and every host that has it
+///
+/// `drift` is set when hosts have different digests under the same name,
+/// e.g. one host pulled `llama3.2:latest` again after an update.
+#[derive(Debug, Clone, Serialize)]
+pub struct InventoryModel {
+    pub name: String,
+    pub placements: Vec<ModelPlacement>,
+    pub digests: Vec<String>,
+    pub drift: bool,
+}
+
+/// Models of all configured hosts merged by name
+#[derive(Debug, Clone, Serialize)]
+pub struct HostInventory {
+    pub hosts: Vec<HostStatus>,
+    pub models: Vec<InventoryModel>,
+    pub drifted_models: usize,
+}
+
+/// The listings of one host, or why they are missing
+pub struct HostListing {
+    pub server: ServerConnection,
+    pub elapsed: Duration,
+    pub result: Result<(Vec<OllamaModel>, Vec<OllamaRunningModel>), String>,
+}
+
+// List installed and running models of one host within `timeout`
+async fn list_host(server: ServerConnection, timeout: Duration) -> HostListing {
+    let started = Instant::now();
+    let result = match OllamaClient::from_server(&server) {
+        Ok(client) => {
+            let listings = async { tokio::try_join!(client.list_models(), client.list_running_models()) };
+            match tokio::time::timeout(timeout, listings).await {
+                Ok(result) => result.map_err(|e| e.to_string()),
+                Err(_) => Err(format!("No answer within {} seconds", timeout.as_secs())),
+            }
+        }
+        Err(e) => Err(e.to_string()),
+    };
+    HostListing { server, elapsed: started.elapsed(), result }
+}
+
+/// Merge the listings of all hosts into one inventory, hosts keep their order
+pub fn merge_inventory(listings: Vec<HostListing>) -> HostInventory {
+    let mut hosts = Vec::new();
+    let mut models: BTreeMap<String, Vec<ModelPlacement>> = BTreeMap::new();
+
+    for listing in listings {
+        let server = listing.server;
+        let mut status = HostStatus {
+            profile: server.profile.clone(),
+            host: server.host.clone(),
+            reachable: false,
+            error: None,
+            elapsed_ms: listing.elapsed.as_millis() as u64,
+            model_count: 0,
+            running_count: 0,
+        };
+
+        match listing.result {
+            Ok((installed, running)) => {
+                status.reachable = true;
+                status.model_count = installed.len();
+                status.running_count = running.len();
+                for model in installed {
+                    let is_running = running.iter().any(|r| r.name == model.name);
+                    models.entry(model.name).or_default().push(ModelPlacement {
+                        profile: server.profile.clone(),
+                        host: server.host.clone(),
+                        digest: model.digest,
+                        size: model.size,
+                        modified_at: model.modified_at,
+                        running: is_running,
+                    });
+                }
+            }
+            Err(error) => {
+                println!("[INVENTORY] Host {} not listed: {}", server.host, error);
+                status.error = Some(error);
+            }
+        }
+        hosts.push(status);
+    }
+
+    let models: Vec<InventoryModel> = models.into_iter()
+        .map(|(name, placements)| {
+            let mut digests: Vec<String> = placements.iter().map(|p| p.digest.clone()).collect();
+            digests.sort();
+            digests.dedup();
+            InventoryModel { name, drift: digests.len() > 1, digests, placements }
+        })
+        .collect();
+    let drifted_models = models.iter().filter(|m| m.drift).count();
+
+    HostInventory { hosts, models, drifted_models }
+}
+
+/// Tauri command: List installed and running models on every configured host at once
+///
+/// Each host gets `timeout_secs` (10 seconds by default) before it counts as unreachable.
+#[tauri::command]
+pub async fn inventory_all_hosts(timeout_secs: Option<u64>) -> Result<HostInventory, OllamaError> {
+    let servers = resolve_all_servers().map_err(OllamaError::Other)?;
+    let timeout = timeout_secs.filter(|secs| *secs > 0).map(Duration::from_secs).unwrap_or(DEFAULT_HOST_TIMEOUT);
+
+    let listings = join_all(servers.into_iter().map(|server| list_host(server, timeout))).await;
+    let inventory = merge_inventory(listings);
+    println!(
+        "[INVENTORY] hosts={}, reachable={}, models={}, drifted={}",
+        inventory.hosts.len(),
+        inventory.hosts.iter().filter(|h| h.reachable).count(),
+        inventory.models.len(),
+        inventory.drifted_models
+    );
+    Ok(inventory)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config_manager::TlsOptions;
+    use serde_json::json;
+
+    fn server(profile: &str, host: &str) -> ServerConnection {
+        ServerConnection {
+            profile: Some(profile.to_string()),
+            host: host.to_string(),
+            headers: Default::default(),
+            auth: Default::default(),
+            tls: TlsOptions::default(),
+            timeouts: Default::default(),
+            models_path: String::new(),
+            models_path_configured: false,
+        }
+    }
+
+    fn model(name: &str, digest: &str) -> OllamaModel {
+        serde_json::from_value(json!({
+            "name": name,
+            "model": name,
+            "modified_at": "2026-10-01T12:00:00Z",
+            "size": 2019393189i64,
+            "digest": digest,
+            "details": {
+                "parent_model": "",
+                "format": "gguf",
+                "family": "llama",
+                "families": ["llama"],
+                "parameter_size": "3.2B",
+                "quantization_level": "Q4_K_M",
+            },
+        }))
+        .unwrap()
+    }
+
+    fn running(name: &str, digest: &str) -> OllamaRunningModel {
+        let mut value = serde_json::to_value(model(name, digest)).unwrap();
+        value["expires_at"] = json!("2026-10-01T12:05:00Z");
+        value["size_vram"] = json!(0);
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_merge_inventory() {
+        let listings = vec![
+            HostListing {
+                server: server("laptop", "http://127.0.0.1:11434"),
+                elapsed: Duration::from_millis(12),
+                result: Ok((vec![model("llama3.2:latest", "aaa"), model("qwen2.5:7b", "ccc")], vec![running("qwen2.5:7b", "ccc")])),
+            },
+            HostListing {
+                server: server("gpu", "http://10.0.0.5:11434"),
+                elapsed: Duration::from_millis(40),
+                result: Ok((vec![model("llama3.2:latest", "bbb"), model("qwen2.5:7b", "ccc")], Vec::new())),
+            },
+            HostListing {
+                server: server("staging", "https://staging.example.com"),
+                elapsed: Duration::from_secs(10),
+                result: Err("No answer within 10 seconds".to_string()),
+            },
+        ];
+
+        let inventory = merge_inventory(listings);
+        let reachable: Vec<bool> = inventory.hosts.iter().map(|h| h.reachable).collect();
+        assert_eq!(reachable, vec![true, true, false]);
+        assert_eq!(inventory.hosts[0].running_count, 1);
+        assert_eq!(inventory.hosts[2].error.as_deref(), Some("No answer within 10 seconds"));
+
+        assert_eq!(inventory.models.len(), 2);
+        let llama = &inventory.models[0];
+        assert_eq!(llama.name, "llama3.2:latest");
+        assert!(llama.drift);
+        assert_eq!(llama.digests, vec!["aaa", "bbb"]);
+
+        let qwen = &inventory.models[1];
+        assert!(!qwen.drift);
+        let running_on: Vec<bool> = qwen.placements.iter().map(|p| p.running).collect();
+        assert_eq!(running_on, vec![true, false]);
+        assert_eq!(inventory.drifted_models, 1);
+    }
+}
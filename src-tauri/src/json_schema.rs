@@ -0,0 +1,366 @@
+use serde::Serialize;
+use serde_json::Value;
+
+// Guard against `$ref` cycles in malformed schemas
+const MAX_REF_DEPTH: usize = 32;
+
+/// A single validation failure, `path` is a JSON pointer into the instance
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct SchemaError {
+    pub path: String,
+    pub message: String,
+}
+
+/// Validate a JSON value against a JSON Schema
+///
+/// Supports the subset of keywords that matter for structured model output:
+/// `type`, `enum`, `const`, `properties`, `required`, `additionalProperties`,
+/// `items`, `minItems`/`maxItems`, `uniqueItems`, `minLength`/`maxLength`,
+/// `minimum`/`maximum` (and their exclusive variants), `multipleOf`,
+/// `allOf`/`anyOf`/`oneOf`, `not` and local `$ref`s (`#/$defs/...`).
+/// Unknown keywords are ignored.
+pub fn validate(schema: &Value, instance: &Value) -> Vec<SchemaError> {
+    let mut errors = Vec::new();
+    Validator { root: schema }.validate(schema, instance, "", 0, &mut errors);
+    errors
+}
+
+/// Format validation errors as a list the model can act on
+pub fn describe_errors(errors: &[SchemaError]) -> String {
+    errors
+        .iter()
+        .map(|e| {
+            let path = if e.path.is_empty() { "/" } else { e.path.as_str() };
+            format!("- {}: {}", path, e.message)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+struct Validator<'a> {
+    root: &'a Value,
+}
+
+impl<'a> Validator<'a> {
+    fn validate(&self, schema: &'a Value, instance: &Value, path: &str, depth: usize, errors: &mut Vec<SchemaError>) {
+        let schema = match schema {
+            // `true` accepts everything, `false` rejects everything
+            Value::Bool(true) => return,
+            Value::Bool(false) => {
+                push(errors, path, "no value is allowed here".to_string());
+                return;
+            }
+            Value::Object(schema) => schema,
+            _ => return,
+        };
+
+        if let Some(reference) = schema.get("$ref").and_then(|r| r.as_str()) {
+            if depth >= MAX_REF_DEPTH {
+                push(errors, path, format!("$ref nesting too deep at {}", reference));
+                return;
+            }
+            match self.resolve_ref(reference) {
+                Some(target) => self.validate(target, instance, path, depth + 1, errors),
+                None => push(errors, path, format!("unresolvable $ref {}", reference)),
+            }
+        }
+
+        if let Some(expected) = schema.get("type") {
+            let matches = match expected {
+                Value::String(t) => type_matches(t, instance),
+                Value::Array(types) => types.iter().filter_map(|t| t.as_str()).any(|t| type_matches(t, instance)),
+                _ => true,
+            };
+            if !matches {
+                push(errors, path, format!("expected type {}, got {}", type_list(expected), type_name(instance)));
+                // The remaining keywords would only repeat the type mismatch
+                return;
+            }
+        }
+
+        if let Some(allowed) = schema.get("enum").and_then(|e| e.as_array()) {
+            if !allowed.contains(instance) {
+                push(errors, path, format!("value {} is not one of {}", instance, Value::Array(allowed.clone())));
+            }
+        }
+
+        if let Some(expected) = schema.get("const") {
+            if expected != instance {
+                push(errors, path, format!("value must be {}", expected));
+            }
+        }
+
+        match instance {
+            Value::Object(object) => self.validate_object(schema, object, path, depth, errors),
+            Value::Array(items) => self.validate_array(schema, items, path, depth, errors),
+            Value::String(s) => validate_string(schema, s, path, errors),
+            Value::Number(n) => {
+                if let Some(n) = n.as_f64() {
+                    validate_number(schema, n, path, errors);
+                }
+            }
+            _ => {}
+        }
+
+        if let Some(all_of) = schema.get("allOf").and_then(|s| s.as_array()) {
+            for sub in all_of {
+                self.validate(sub, instance, path, depth, errors);
+            }
+        }
+
+        if let Some(any_of) = schema.get("anyOf").and_then(|s| s.as_array()) {
+            if !any_of.iter().any(|sub| self.is_valid(sub, instance, depth)) {
+                push(errors, path, "value does not match any of the allowed schemas (anyOf)".to_string());
+            }
+        }
+
+        if let Some(one_of) = schema.get("oneOf").and_then(|s| s.as_array()) {
+            let matching = one_of.iter().filter(|sub| self.is_valid(sub, instance, depth)).count();
+            if matching != 1 {
+                push(errors, path, format!("value must match exactly one schema (oneOf), matched {}", matching));
+            }
+        }
+
+        if let Some(not) = schema.get("not") {
+            if self.is_valid(not, instance, depth) {
+                push(errors, path, "value matches a schema it must not match (not)".to_string());
+            }
+        }
+    }
+
+    fn validate_object(
+        &self,
+        schema: &'a serde_json::Map<String, Value>,
+        object: &serde_json::Map<String, Value>,
+        path: &str,
+        depth: usize,
+        errors: &mut Vec<SchemaError>,
+    ) {
+        if let Some(required) = schema.get("required").and_then(|r| r.as_array()) {
+            for key in required.iter().filter_map(|k| k.as_str()) {
+                if !object.contains_key(key) {
+                    push(errors, path, format!("missing required property \"{}\"", key));
+                }
+            }
+        }
+
+        let properties = schema.get("properties").and_then(|p| p.as_object());
+        for (key, value) in object {
+            let child_path = format!("{}/{}", path, escape_pointer(key));
+            match properties.and_then(|p| p.get(key)) {
+                Some(property_schema) => self.validate(property_schema, value, &child_path, depth, errors),
+                None => match schema.get("additionalProperties") {
+                    Some(Value::Bool(false)) => {
+                        push(errors, path, format!("unexpected property \"{}\"", key));
+                    }
+                    Some(additional) => self.validate(additional, value, &child_path, depth, errors),
+                    None => {}
+                },
+            }
+        }
+    }
+
+    fn validate_array(
+        &self,
+        schema: &'a serde_json::Map<String, Value>,
+        items: &[Value],
+        path: &str,
+        depth: usize,
+        errors: &mut Vec<SchemaError>,
+    ) {
+        if let Some(min) = schema.get("minItems").and_then(|m| m.as_u64()) {
+            if (items.len() as u64) < min {
+                push(errors, path, format!("expected at least {} items, got {}", min, items.len()));
+            }
+        }
+        if let Some(max) = schema.get("maxItems").and_then(|m| m.as_u64()) {
+            if items.len() as u64 > max {
+                push(errors, path, format!("expected at most {} items, got {}", max, items.len()));
+            }
+        }
+        if schema.get("uniqueItems").and_then(|u| u.as_bool()) == Some(true) {
+            for (i, item) in items.iter().enumerate() {
+                if items[..i].contains(item) {
+                    push(errors, path, format!("item {} is a duplicate", i));
+                }
+            }
+        }
+        if let Some(item_schema) = schema.get("items") {
+            for (i, item) in items.iter().enumerate() {
+                self.validate(item_schema, item, &format!("{}/{}", path, i), depth, errors);
+            }
+        }
+    }
+
+    fn is_valid(&self, schema: &'a Value, instance: &Value, depth: usize) -> bool {
+        let mut errors = Vec::new();
+        self.validate(schema, instance, "", depth, &mut errors);
+        errors.is_empty()
+    }
+
+    /// Resolve a local reference such as `#/$defs/Person` against the root schema
+    fn resolve_ref(&self, reference: &str) -> Option<&'a Value> {
+        let pointer = reference.strip_prefix('#')?;
+        if pointer.is_empty() {
+            Some(self.root)
+        } else {
+            self.root.pointer(pointer)
+        }
+    }
+}
+
+fn validate_string(schema: &serde_json::Map<String, Value>, s: &str, path: &str, errors: &mut Vec<SchemaError>) {
+    let length = s.chars().count() as u64;
+    if let Some(min) = schema.get("minLength").and_then(|m| m.as_u64()) {
+        if length < min {
+            push(errors, path, format!("string is shorter than {} characters", min));
+        }
+    }
+    if let Some(max) = schema.get("maxLength").and_then(|m| m.as_u64()) {
+        if length > max {
+            push(errors, path, format!("string is longer than {} characters", max));
+        }
+    }
+}
+
+fn validate_number(schema: &serde_json::Map<String, Value>, n: f64, path: &str, errors: &mut Vec<SchemaError>) {
+    if let Some(min) = schema.get("minimum").and_then(|m| m.as_f64()) {
+        if n < min {
+            push(errors, path, format!("{} is less than the minimum {}", n, min));
+        }
+    }
+    if let Some(max) = schema.get("maximum").and_then(|m| m.as_f64()) {
+        if n > max {
+            push(errors, path, format!("{} is greater than the maximum {}", n, max));
+        }
+    }
+    if let Some(min) = schema.get("exclusiveMinimum").and_then(|m| m.as_f64()) {
+        if n <= min {
+            push(errors, path, format!("{} must be greater than {}", n, min));
+        }
+    }
+    if let Some(max) = schema.get("exclusiveMaximum").and_then(|m| m.as_f64()) {
+        if n >= max {
+            push(errors, path, format!("{} must be less than {}", n, max));
+        }
+    }
+    if let Some(step) = schema.get("multipleOf").and_then(|m| m.as_f64()) {
+        if step > 0.0 && ((n / step).round() * step - n).abs() > 1e-9 {
+            push(errors, path, format!("{} is not a multiple of {}", n, step));
+        }
+    }
+}
+
+fn type_matches(expected: &str, instance: &Value) -> bool {
+    match expected {
+        "object" => instance.is_object(),
+        "array" => instance.is_array(),
+        "string" => instance.is_string(),
+        "boolean" => instance.is_boolean(),
+        "null" => instance.is_null(),
+        "number" => instance.is_number(),
+        // 1.0 is a valid integer in JSON Schema
+        "integer" => instance.as_f64().is_some_and(|n| n.fract() == 0.0),
+        _ => true,
+    }
+}
+
+fn type_name(instance: &Value) -> &'static str {
+    match instance {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(n) if n.is_i64() || n.is_u64() => "integer",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+fn type_list(expected: &Value) -> String {
+    match expected {
+        Value::Array(types) => types.iter().filter_map(|t| t.as_str()).collect::<Vec<_>>().join(" or "),
+        other => other.as_str().unwrap_or_default().to_string(),
+    }
+}
+
+fn escape_pointer(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+fn push(errors: &mut Vec<SchemaError>, path: &str, message: String) {
+    errors.push(SchemaError {
+        path: path.to_string(),
+        message,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn person_schema() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "name": { "type": "string", "minLength": 1 },
+                "age": { "type": "integer", "minimum": 0 },
+                "tags": { "type": "array", "items": { "type": "string" } },
+                "address": { "$ref": "#/$defs/Address" }
+            },
+            "required": ["name", "age"],
+            "additionalProperties": false,
+            "$defs": {
+                "Address": {
+                    "type": "object",
+                    "properties": { "city": { "type": "string" } },
+                    "required": ["city"]
+                }
+            }
+        })
+    }
+
+    #[test]
+    fn test_valid_instance_has_no_errors() {
+        let instance = json!({
+            "name": "Ada",
+            "age": 36,
+            "tags": ["math"],
+            "address": { "city": "London" }
+        });
+        assert!(validate(&person_schema(), &instance).is_empty());
+    }
+
+    #[test]
+    fn test_errors_point_at_the_offending_value() {
+        let instance = json!({
+            "name": "",
+            "age": -1.5,
+            "tags": ["ok", 3],
+            "address": {},
+            "extra": true
+        });
+        let errors = validate(&person_schema(), &instance);
+        let paths: Vec<&str> = errors.iter().map(|e| e.path.as_str()).collect();
+
+        assert!(paths.contains(&"/name"));
+        assert!(paths.contains(&"/age"));
+        assert!(paths.contains(&"/tags/1"));
+        assert!(paths.contains(&"/address"));
+        assert!(errors.iter().any(|e| e.path.is_empty() && e.message.contains("extra")));
+    }
+
+    #[test]
+    fn test_combinators_and_enum() {
+        let schema = json!({
+            "anyOf": [
+                { "type": "string", "enum": ["low", "high"] },
+                { "type": "null" }
+            ]
+        });
+        assert!(validate(&schema, &json!("low")).is_empty());
+        assert!(validate(&schema, &Value::Null).is_empty());
+        assert_eq!(validate(&schema, &json!("medium")).len(), 1);
+    }
+}
@@ -11,9 +11,24 @@ mod system_monitor;
 use system_monitor::{SystemInfo, get_system_info, get_system_info_for_path};
 
 mod config_manager;
-use config_manager::{get_ollama_host, set_ollama_host, clear_ollama_host, get_config_info, get_ollama_models_path, set_ollama_models_path, clear_ollama_models_path, restart_ollama_service, check_ollama_service_status};
+use config_manager::{get_ollama_host, set_ollama_host, clear_ollama_host, get_config_info, get_ollama_models_path, set_ollama_models_path, clear_ollama_models_path, get_max_concurrent_downloads, set_max_concurrent_downloads, get_download_window, set_download_window, get_server_profiles, save_server_profile, delete_server_profile, set_active_profile, get_host_auth, set_host_auth, clear_host_auth, restart_ollama_service, check_ollama_service_status};
 
 mod ollama_api;
+mod ollama_client;
+mod ollama_error;
+mod json_schema;
+mod download_queue;
+mod pull_progress;
+mod pull_retry;
+mod download_schedule;
+mod model_store;
+mod model_migration;
+mod model_import;
+mod modelfile;
+mod model_diff;
+mod host_inventory;
+mod model_transfer;
+mod tools;
 
 // Window state structure for serialization and deserialization
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -467,6 +482,17 @@ fn main() {
             get_ollama_models_path,
             set_ollama_models_path,
             clear_ollama_models_path,
+            get_max_concurrent_downloads,
+            set_max_concurrent_downloads,
+            get_download_window,
+            set_download_window,
+            get_server_profiles,
+            save_server_profile,
+            delete_server_profile,
+            set_active_profile,
+            get_host_auth,
+            set_host_auth,
+            clear_host_auth,
             restart_ollama_service,
             check_ollama_service_status,
             restart_ollama,
@@ -477,9 +503,19 @@ fn main() {
             ollama_api::list_models,
             ollama_api::list_running_models,
             ollama_api::show_model_info,
+            ollama_api::verify_model,
+            ollama_api::inspect_model_store,
+            ollama_api::gc_models_dir,
+            model_migration::migrate_models_dir,
+            model_import::import_model_file,
+            modelfile::parse_modelfile,
+            model_diff::diff_models,
+            host_inventory::inventory_all_hosts,
+            model_transfer::transfer_model,
             ollama_api::copy_model,
             ollama_api::delete_model,
             ollama_api::generate_completion,
+            ollama_api::generate_completion_stream,
             ollama_api::generate_chat,
             ollama_api::generate_embeddings,
             ollama_api::create_model,
@@ -487,9 +523,18 @@ fn main() {
             ollama_api::unload_model,
             ollama_api::pull_model,
             ollama_api::cancel_pull,
+            ollama_api::pause_pull,
+            ollama_api::resume_pull,
+            ollama_api::check_download_status,
+            ollama_api::get_download_queue,
+            ollama_api::move_download,
+            ollama_api::set_download_priority,
             ollama_api::push_model,
             ollama_api::validate_host,
-            ollama_api::generate_chat_completion
+            ollama_api::generate_chat_completion,
+            ollama_api::cancel_generation,
+            ollama_api::chat_with_tools,
+            ollama_api::generate_structured
         ])
         .setup(|app| {
             // We can get app_handle, but actually Tauri will automatically inject it
@@ -565,6 +610,11 @@ fn main() {
                 }
             });
             
+            // Resume the downloads that were queued when the app last exited
+            ollama_api::restore_download_queue(&app.handle());
+            // Pause and resume downloads at the edges of the configured download window
+            ollama_api::start_download_scheduler(app.handle());
+            
             Ok(())
         })
         .run(tauri::generate_context!())
@@ -0,0 +1,424 @@
+use crate::model_store::{blob_digest, Manifest, ModelRef, ModelStore};
+use crate::modelfile::{Directive, Modelfile};
+use crate::ollama_api::ModelInfo;
+use crate::ollama_client::OllamaClient;
+use crate::ollama_error::OllamaError;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+/// A PARAMETER that differs, with all values since `stop` may repeat
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ParameterChange {
+    pub name: String,
+    pub kind: ChangeKind,
+    pub before: Vec<String>,
+    pub after: Vec<String>,
+}
+
+/// Modelfile instructions besides FROM, PARAMETER, TEMPLATE and SYSTEM that differ
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct InstructionChange {
+    pub directive: String,
+    pub kind: ChangeKind,
+    pub before: Vec<String>,
+    pub after: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LineOp {
+    Same,
+    Added,
+    Removed,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DiffLine {
+    pub op: LineOp,
+    pub text: String,
+}
+
+/// Line diff of a text field such as the template or system prompt
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TextDiff {
+    pub before: String,
+    pub after: String,
+    pub lines: Vec<DiffLine>,
+}
+
+/// A `details` field that differs
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DetailChange {
+    pub field: String,
+    pub before: Value,
+    pub after: Value,
+}
+
+/// A layer blob, media type and size are only known from a manifest
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LayerRef {
+    pub digest: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub media_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size: Option<u64>,
+}
+
+/// Layers of both models
+///
+/// With local manifests for both models all layers are compared, otherwise only
+/// the blobs the modelfiles reference in FROM and ADAPTER lines.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LayerDiff {
+    pub from_manifests: bool,
+    pub shared: Vec<LayerRef>,
+    pub only_base: Vec<LayerRef>,
+    pub only_other: Vec<LayerRef>,
+}
+
+/// Structural difference between two models, from `base` to `other`
+#[derive(Debug, Clone, Serialize)]
+pub struct ModelDiff {
+    pub base: String,
+    pub other: String,
+    pub identical: bool,
+    pub parameters: Vec<ParameterChange>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub template: Option<TextDiff>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub system: Option<TextDiff>,
+    pub instructions: Vec<InstructionChange>,
+    pub details: Vec<DetailChange>,
+    pub layers: LayerDiff,
+}
+
+/// One side of a diff: the `/api/show` response and the local manifest if there is one
+pub struct ModelSide<'a> {
+    pub name: &'a str,
+    pub info: &'a ModelInfo,
+    pub manifest: Option<&'a Manifest>,
+}
+
+// Instructions of a modelfile grouped by directive, in file order
+struct Instructions {
+    parameters: BTreeMap<String, Vec<String>>,
+    system: String,
+    others: BTreeMap<&'static str, Vec<String>>,
+    blobs: Vec<String>,
+}
+
+impl Instructions {
+    fn from_modelfile(modelfile: &str) -> Self {
+        let (modelfile, _) = Modelfile::parse(modelfile);
+        let mut instructions = Instructions {
+            parameters: BTreeMap::new(),
+            system: String::new(),
+            others: BTreeMap::new(),
+            blobs: Vec::new(),
+        };
+
+        for directive in modelfile.directives() {
+            match directive {
+                Directive::From { model } => instructions.blobs.extend(path_digest(model)),
+                Directive::Parameter { name, value } => {
+                    instructions.parameters.entry(name.clone()).or_default().push(value.clone())
+                }
+                // The template is compared from its own field
+                Directive::Template { .. } => {}
+                Directive::System { system } => instructions.system = system.clone(),
+                Directive::Adapter { path } => {
+                    instructions.blobs.extend(path_digest(path));
+                    instructions.others.entry("ADAPTER").or_default().push(path.clone());
+                }
+                Directive::License { license } => instructions.others.entry("LICENSE").or_default().push(license.clone()),
+                Directive::Message { role, content } => {
+                    instructions.others.entry("MESSAGE").or_default().push(format!("{}: {}", role.as_str(), content))
+                }
+                Directive::Requires { version } => instructions.others.entry("REQUIRES").or_default().push(version.clone()),
+            }
+        }
+        instructions
+    }
+}
+
+// `sha256:<hex>` of a FROM or ADAPTER value that points into a blobs directory
+fn path_digest(value: &str) -> Option<String> {
+    Path::new(value).file_name().and_then(|name| blob_digest(&name.to_string_lossy()))
+}
+
+// Compare two maps of value lists, keyed by name
+fn diff_lists<K: Ord + Clone>(before: &BTreeMap<K, Vec<String>>, after: &BTreeMap<K, Vec<String>>) -> Vec<(K, ChangeKind, Vec<String>, Vec<String>)> {
+    let keys: BTreeSet<&K> = before.keys().chain(after.keys()).collect();
+    keys.into_iter()
+        .filter_map(|key| {
+            let (kind, old, new) = match (before.get(key), after.get(key)) {
+                (Some(old), Some(new)) if old == new => return None,
+                (Some(old), Some(new)) => (ChangeKind::Changed, old.clone(), new.clone()),
+                (Some(old), None) => (ChangeKind::Removed, old.clone(), Vec::new()),
+                (None, Some(new)) => (ChangeKind::Added, Vec::new(), new.clone()),
+                (None, None) => return None,
+            };
+            Some((key.clone(), kind, old, new))
+        })
+        .collect()
+}
+
+/// Line diff by longest common subsequence, `None` when both texts are equal
+pub fn diff_text(before: &str, after: &str) -> Option<TextDiff> {
+    if before == after {
+        return None;
+    }
+    let old: Vec<&str> = before.lines().collect();
+    let new: Vec<&str> = after.lines().collect();
+
+    // common[i][j] = length of the LCS of old[i..] and new[j..]
+    let mut common = vec![vec![0u32; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            common[i][j] = if old[i] == new[j] {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
+        }
+    }
+
+    let line = |op, text: &str| DiffLine { op, text: text.to_string() };
+    let mut lines = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            lines.push(line(LineOp::Same, old[i]));
+            i += 1;
+            j += 1;
+        } else if common[i + 1][j] >= common[i][j + 1] {
+            lines.push(line(LineOp::Removed, old[i]));
+            i += 1;
+        } else {
+            lines.push(line(LineOp::Added, new[j]));
+            j += 1;
+        }
+    }
+    lines.extend(old[i..].iter().map(|text| line(LineOp::Removed, text)));
+    lines.extend(new[j..].iter().map(|text| line(LineOp::Added, text)));
+
+    Some(TextDiff { before: before.to_string(), after: after.to_string(), lines })
+}
+
+fn diff_details(base: &ModelInfo, other: &ModelInfo) -> Vec<DetailChange> {
+    let as_map = |info: &ModelInfo| match serde_json::to_value(&info.details) {
+        Ok(Value::Object(map)) => map,
+        _ => Default::default(),
+    };
+    let (before, after) = (as_map(base), as_map(other));
+    let fields: BTreeSet<&String> = before.keys().chain(after.keys()).collect();
+    fields.into_iter()
+        .filter_map(|field| {
+            let old = before.get(field).cloned().unwrap_or(Value::Null);
+            let new = after.get(field).cloned().unwrap_or(Value::Null);
+            (old != new).then(|| DetailChange { field: field.clone(), before: old, after: new })
+        })
+        .collect()
+}
+
+// Layers of a side: the manifest if it describes this model, else the referenced blobs
+fn side_layers(side: &ModelSide, referenced: &[String]) -> Option<Vec<LayerRef>> {
+    let manifest = side.manifest?;
+    // A local manifest under the same name may be a different model than the server's
+    let digests: BTreeSet<&str> = manifest.layers.iter().map(|l| l.digest.as_str()).collect();
+    if !referenced.iter().all(|d| digests.contains(d.as_str())) {
+        return None;
+    }
+    Some(manifest.layers.iter()
+        .map(|l| LayerRef { digest: l.digest.clone(), media_type: Some(l.media_type.clone()), size: Some(l.size) })
+        .collect())
+}
+
+fn diff_layers(base: Vec<LayerRef>, other: Vec<LayerRef>, from_manifests: bool) -> LayerDiff {
+    let other_digests: BTreeSet<String> = other.iter().map(|l| l.digest.clone()).collect();
+    let base_digests: BTreeSet<String> = base.iter().map(|l| l.digest.clone()).collect();
+    let (shared, only_base) = base.into_iter().partition(|l| other_digests.contains(&l.digest));
+    let only_other = other.into_iter().filter(|l| !base_digests.contains(&l.digest)).collect();
+    LayerDiff { from_manifests, shared, only_base, only_other }
+}
+
+/// Compare two models structurally
+pub fn diff(base: &ModelSide, other: &ModelSide) -> ModelDiff {
+    let old = Instructions::from_modelfile(&base.info.modelfile);
+    let new = Instructions::from_modelfile(&other.info.modelfile);
+
+    let parameters: Vec<ParameterChange> = diff_lists(&old.parameters, &new.parameters)
+        .into_iter()
+        .map(|(name, kind, before, after)| ParameterChange { name, kind, before, after })
+        .collect();
+    let instructions: Vec<InstructionChange> = diff_lists(&old.others, &new.others)
+        .into_iter()
+        .map(|(directive, kind, before, after)| InstructionChange { directive: directive.to_string(), kind, before, after })
+        .collect();
+    let template = diff_text(&base.info.template, &other.info.template);
+    let system = diff_text(&old.system, &new.system);
+    let details = diff_details(base.info, other.info);
+
+    let layers = match (side_layers(base, &old.blobs), side_layers(other, &new.blobs)) {
+        (Some(base_layers), Some(other_layers)) => diff_layers(base_layers, other_layers, true),
+        _ => {
+            let referenced = |blobs: Vec<String>| blobs.into_iter()
+                .map(|digest| LayerRef { digest, media_type: None, size: None })
+                .collect();
+            diff_layers(referenced(old.blobs), referenced(new.blobs), false)
+        }
+    };
+
+    let identical = parameters.is_empty()
+        && instructions.is_empty()
+        && template.is_none()
+        && system.is_none()
+        && details.is_empty()
+        && layers.only_base.is_empty()
+        && layers.only_other.is_empty();
+
+    ModelDiff {
+        base: base.name.to_string(),
+        other: other.name.to_string(),
+        identical,
+        parameters,
+        template,
+        system,
+        instructions,
+        details,
+        layers,
+    }
+}
+
+// Local manifest of a model, `None` when there is no local models directory or manifest
+fn local_manifest(store: Option<&ModelStore>, model: &str) -> Option<Manifest> {
+    let model = ModelRef::parse(model).ok()?;
+    store?.read_manifest(&model).ok()
+}
+
+/// Tauri command: Compare the modelfiles, templates, parameters, details and layers of two models
+#[tauri::command]
+pub async fn diff_models(base: String, other: String, profile: Option<String>) -> Result<ModelDiff, OllamaError> {
+    let client = OllamaClient::from_profile(profile.as_deref())?;
+    let (base_info, other_info) = tokio::try_join!(client.show_model(&base, false), client.show_model(&other, false))?;
+
+    let store = ModelStore::from_profile(profile.as_deref()).ok();
+    let base_manifest = local_manifest(store.as_ref(), &base);
+    let other_manifest = local_manifest(store.as_ref(), &other);
+
+    let result = diff(
+        &ModelSide { name: &base, info: &base_info, manifest: base_manifest.as_ref() },
+        &ModelSide { name: &other, info: &other_info, manifest: other_manifest.as_ref() },
+    );
+    println!(
+        "[DIFF] {} -> {}: identical={}, parameters={}, shared_layers={}",
+        base, other, result.identical, result.parameters.len(), result.layers.shared.len()
+    );
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const WEIGHTS: &str = "sha256:1111111111111111111111111111111111111111111111111111111111111111";
+    const ADAPTER: &str = "sha256:2222222222222222222222222222222222222222222222222222222222222222";
+
+    fn model_info(modelfile: &str, template: &str, quantization: &str) -> ModelInfo {
+        serde_json::from_value(json!({
+            "modelfile": modelfile,
+            "parameters": null,
+            "template": template,
+            "details": {
+                "parent_model": "",
+                "format": "gguf",
+                "family": "llama",
+                "families": ["llama"],
+                "parameter_size": "3.2B",
+                "quantization_level": quantization,
+            },
+            "model_info": {},
+            "capabilities": ["completion"],
+        }))
+        .unwrap()
+    }
+
+    fn manifest(layers: &[&str]) -> Manifest {
+        serde_json::from_value(json!({
+            "schemaVersion": 2,
+            "config": { "mediaType": "application/vnd.docker.container.image.v1+json", "digest": ADAPTER, "size": 1 },
+            "layers": layers.iter().map(|d| json!({ "mediaType": "application/vnd.ollama.image.model", "digest": d, "size": 10 })).collect::<Vec<_>>(),
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_diff_models() {
+        let blob = |digest: &str| format!("/models/blobs/{}", digest.replace(':', "-"));
+        let base = model_info(
+            &format!("# Modelfile generated by \"ollama show\"\nFROM {}\nPARAMETER temperature 0.7\nPARAMETER stop \"<|eot_id|>\"\nPARAMETER top_k 40\nSYSTEM You are terse.\n", blob(WEIGHTS)),
+            "{{ .System }}\n{{ .Prompt }}",
+            "Q4_K_M",
+        );
+        let other = model_info(
+            &format!("FROM {}\nADAPTER {}\nPARAMETER temperature 0.2\nPARAMETER stop \"<|eot_id|>\"\nPARAMETER num_ctx 8192\nSYSTEM You are terse.\n", blob(WEIGHTS), blob(ADAPTER)),
+            "{{ .System }}\nUser: {{ .Prompt }}",
+            "Q8_0",
+        );
+
+        let result = diff(
+            &ModelSide { name: "base", info: &base, manifest: None },
+            &ModelSide { name: "tuned", info: &other, manifest: None },
+        );
+        assert!(!result.identical);
+        let changes: Vec<(&str, ChangeKind)> = result.parameters.iter().map(|p| (p.name.as_str(), p.kind)).collect();
+        assert_eq!(changes, vec![("num_ctx", ChangeKind::Added), ("temperature", ChangeKind::Changed), ("top_k", ChangeKind::Removed)]);
+        assert!(result.system.is_none());
+        assert_eq!(result.instructions[0].directive, "ADAPTER");
+
+        let template = result.template.unwrap();
+        let ops: Vec<LineOp> = template.lines.iter().map(|l| l.op).collect();
+        assert_eq!(ops, vec![LineOp::Same, LineOp::Removed, LineOp::Added]);
+
+        assert_eq!(result.details, vec![DetailChange { field: "quantization_level".to_string(), before: json!("Q4_K_M"), after: json!("Q8_0") }]);
+
+        // Without manifests only the referenced blobs are compared
+        assert!(!result.layers.from_manifests);
+        assert_eq!(result.layers.shared.len(), 1);
+        assert_eq!(result.layers.only_other[0].digest, ADAPTER);
+
+        // Manifests that match the modelfile are used; one that does not is ignored
+        let base_manifest = manifest(&[WEIGHTS]);
+        let other_manifest = manifest(&[WEIGHTS, ADAPTER]);
+        let result = diff(
+            &ModelSide { name: "base", info: &base, manifest: Some(&base_manifest) },
+            &ModelSide { name: "tuned", info: &other, manifest: Some(&other_manifest) },
+        );
+        assert!(result.layers.from_manifests);
+        assert_eq!(result.layers.only_other[0].size, Some(10));
+
+        let stale = manifest(&[ADAPTER]);
+        let result = diff(
+            &ModelSide { name: "base", info: &base, manifest: Some(&stale) },
+            &ModelSide { name: "tuned", info: &other, manifest: Some(&other_manifest) },
+        );
+        assert!(!result.layers.from_manifests);
+
+        let same = diff(
+            &ModelSide { name: "base", info: &base, manifest: None },
+            &ModelSide { name: "copy", info: &base, manifest: None },
+        );
+        assert!(same.identical);
+    }
+}
@@ -0,0 +1,262 @@
+use crate::model_store::sha256_file;
+use crate::ollama_api::{forward_status_stream, register_generation};
+use crate::ollama_client::OllamaClient;
+use crate::ollama_error::OllamaError;
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::Manager;
+use tokio::io::AsyncReadExt;
+
+const UPLOAD_CHUNK_SIZE: usize = 1024 * 1024;
+// Emit an upload event at least every this many bytes
+const UPLOAD_PROGRESS_STEP: u64 = 32 * 1024 * 1024;
+
+/// Progress event of `import_model_file` and `transfer_model`
+///
+/// Hashing and upload events name the local `file`; the status lines of the
+/// final `/api/create` follow as pull-style progress events.
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct ImportModelProgress {
+    pub status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub digest: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub completed: Option<u64>,
+}
+
+/// A local file that becomes a blob of the model, `name` is its key in the `files` map
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportFile {
+    pub name: String,
+    pub path: PathBuf,
+    pub size: u64,
+}
+
+/// The files to upload for a `.gguf` file or a safetensors model directory
+///
+/// A safetensors directory contributes its weights plus the config and
+/// tokenizer files Ollama needs to convert it.
+pub fn collect_import_files(path: &Path) -> Result<Vec<ImportFile>, String> {
+    let metadata = fs::metadata(path).map_err(|e| format!("Cannot read {}: {}", path.display(), e))?;
+
+    if metadata.is_file() {
+        if !has_extension(path, "gguf") {
+            return Err(format!("{} is not a .gguf file, select a GGUF file or a safetensors directory", path.display()));
+        }
+        return Ok(vec![import_file(path, metadata.len())]);
+    }
+
+    let mut files = Vec::new();
+    let entries = fs::read_dir(path).map_err(|e| format!("Cannot read {}: {}", path.display(), e))?;
+    for entry in entries {
+        let entry = entry.map_err(|e| e.to_string())?;
+        let file_path = entry.path();
+        let metadata = entry.metadata().map_err(|e| e.to_string())?;
+        if metadata.is_file() && is_safetensors_model_file(&file_path) {
+            files.push(import_file(&file_path, metadata.len()));
+        }
+    }
+
+    if !files.iter().any(|f| has_extension(&f.path, "safetensors")) {
+        return Err(format!("No .safetensors files in {}", path.display()));
+    }
+    files.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(files)
+}
+
+fn import_file(path: &Path, size: u64) -> ImportFile {
+    ImportFile {
+        name: path.file_name().unwrap_or_default().to_string_lossy().to_string(),
+        path: path.to_path_buf(),
+        size,
+    }
+}
+
+fn has_extension(path: &Path, extension: &str) -> bool {
+    path.extension().is_some_and(|e| e.eq_ignore_ascii_case(extension))
+}
+
+// Weights, config.json, tokenizer*.json, tokenizer.model and tiktoken vocabularies
+fn is_safetensors_model_file(path: &Path) -> bool {
+    ["safetensors", "json", "tiktoken"].iter().any(|e| has_extension(path, e))
+        || path.file_name().is_some_and(|n| n == "tokenizer.model")
+}
+
+fn emit_progress(app_handle: &tauri::AppHandle, channel_id: &str, progress: ImportModelProgress) {
+    let _ = app_handle.emit_all(channel_id, progress);
+}
+
+// Stream a file as request body, reporting the uploaded bytes on the channel
+fn upload_body(file: tokio::fs::File, import_file: &ImportFile, digest: &str, channel_id: &str, app_handle: &tauri::AppHandle) -> reqwest::Body {
+    let template = ImportModelProgress {
+        status: "uploading".to_string(),
+        file: Some(import_file.name.clone()),
+        digest: Some(digest.to_string()),
+        total: Some(import_file.size),
+        completed: Some(0),
+    };
+    let channel_id = channel_id.to_string();
+    let app_handle = app_handle.clone();
+
+    let stream = futures_util::stream::try_unfold((file, 0u64, 0u64), move |(mut file, sent, reported)| {
+        let template = template.clone();
+        let channel_id = channel_id.clone();
+        let app_handle = app_handle.clone();
+        async move {
+            let mut buffer = vec![0u8; UPLOAD_CHUNK_SIZE];
+            let read = file.read(&mut buffer).await?;
+            if read == 0 {
+                return Ok::<_, std::io::Error>(None);
+            }
+            buffer.truncate(read);
+
+            let sent = sent + read as u64;
+            let mut reported = reported;
+            if sent - reported >= UPLOAD_PROGRESS_STEP || Some(sent) == template.total {
+                reported = sent;
+                emit_progress(&app_handle, &channel_id, ImportModelProgress { completed: Some(sent), ..template });
+            }
+            Ok(Some((buffer, (file, sent, reported))))
+        }
+    });
+    reqwest::Body::wrap_stream(stream)
+}
+
+// Hash a file off the async runtime, returns its digest
+pub(crate) async fn hash_file(file: &ImportFile, channel_id: &str, app_handle: &tauri::AppHandle) -> Result<String, OllamaError> {
+    emit_progress(app_handle, channel_id, ImportModelProgress {
+        status: "hashing".to_string(),
+        file: Some(file.name.clone()),
+        total: Some(file.size),
+        ..Default::default()
+    });
+
+    // Hashing multi-gigabyte weights blocks, keep it off the async runtime
+    let path = file.path.clone();
+    tokio::task::spawn_blocking(move || sha256_file(&path))
+        .await
+        .map_err(|e| OllamaError::Other(format!("Hashing task failed: {}", e)))?
+        .map_err(|e| OllamaError::Other(format!("Failed to hash {}: {}", file.path.display(), e)))
+}
+
+// Upload a file as blob `digest` unless the server already has it, `on_server` is the caller's blob_exists answer
+pub(crate) async fn upload_blob(client: &OllamaClient, file: &ImportFile, digest: &str, on_server: bool, channel_id: &str, app_handle: &tauri::AppHandle) -> Result<(), OllamaError> {
+    if on_server {
+        println!("[IMPORT] Blob already on the server: file={}, digest={}", file.name, digest);
+        emit_progress(app_handle, channel_id, ImportModelProgress {
+            status: "blob_exists".to_string(),
+            file: Some(file.name.clone()),
+            digest: Some(digest.to_string()),
+            total: Some(file.size),
+            completed: Some(file.size),
+        });
+        return Ok(());
+    }
+
+    println!("[IMPORT] Uploading blob: file={}, digest={}, size={}", file.name, digest, file.size);
+    let handle = tokio::fs::File::open(&file.path)
+        .await
+        .map_err(|e| OllamaError::Other(format!("Failed to open {}: {}", file.path.display(), e)))?;
+    client.push_blob(digest, upload_body(handle, file, digest, channel_id, app_handle)).await
+}
+
+// Hash a file and upload it unless the server already has the blob, returns the digest
+async fn upload_file(client: &OllamaClient, file: &ImportFile, channel_id: &str, app_handle: &tauri::AppHandle) -> Result<String, OllamaError> {
+    let digest = hash_file(file, channel_id, app_handle).await?;
+    let on_server = client.blob_exists(&digest).await?;
+    upload_blob(client, file, &digest, on_server, channel_id, app_handle).await?;
+    Ok(digest)
+}
+
+// Upload every file, then create the model from the `files` map and forward its status lines
+async fn import_files(
+    client: &OllamaClient,
+    model_name: &str,
+    files: &[ImportFile],
+    quantize: Option<String>,
+    channel_id: &str,
+    app_handle: &tauri::AppHandle,
+) -> Result<(), OllamaError> {
+    let mut digests = Map::new();
+    for file in files {
+        let digest = upload_file(client, file, channel_id, app_handle).await?;
+        digests.insert(file.name.clone(), json!(digest));
+    }
+
+    let mut payload = json!({
+        "model": model_name,
+        "files": Value::Object(digests),
+    });
+    if let Some(quantize) = quantize {
+        payload["quantize"] = json!(quantize);
+    }
+
+    let response = client.create_stream(&payload).await?;
+    forward_status_stream(response, channel_id, app_handle).await
+}
+
+/// Tauri command: Create a model from a local `.gguf` file or safetensors directory
+///
+/// Progress goes to `channel_id`, which also works as the request id for `cancel_generation`.
+#[tauri::command]
+pub async fn import_model_file(
+    model_name: String,
+    path: String,
+    channel_id: String,
+    quantize: Option<String>,
+    profile: Option<String>,
+    app_handle: tauri::AppHandle,
+) -> Result<String, OllamaError> {
+    let (mut cancel_rx, _guard) = register_generation(&channel_id)?;
+    let client = OllamaClient::from_profile(profile.as_deref())?;
+    let files = collect_import_files(Path::new(&path)).map_err(OllamaError::Other)?;
+    println!("[IMPORT] Importing {} as {}: {} file(s)", path, model_name, files.len());
+
+    tokio::select! {
+        _ = &mut cancel_rx => {
+            // Dropping the future aborts the running upload or create request
+            println!("[IMPORT] Import cancelled: channel_id={}", channel_id);
+            Err(OllamaError::Cancelled)
+        }
+        result = import_files(&client, &model_name, &files, quantize, &channel_id, &app_handle) => {
+            result?;
+            Ok(format!("Model {} imported successfully", model_name))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_collect_import_files() {
+        let dir = std::env::temp_dir().join(format!("ollama-pro-import-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        for name in ["model-00002.safetensors", "model-00001.safetensors", "config.json", "tokenizer.model", "README.md"] {
+            fs::write(dir.join(name), name).unwrap();
+        }
+        fs::write(dir.join("model.gguf"), b"GGUF").unwrap();
+
+        let names: Vec<String> = collect_import_files(&dir).unwrap().into_iter().map(|f| f.name).collect();
+        assert_eq!(names, vec!["config.json", "model-00001.safetensors", "model-00002.safetensors", "tokenizer.model"]);
+
+        let gguf = collect_import_files(&dir.join("model.gguf")).unwrap();
+        assert_eq!(gguf, vec![ImportFile { name: "model.gguf".to_string(), path: dir.join("model.gguf"), size: 4 }]);
+
+        assert!(collect_import_files(&dir.join("README.md")).is_err());
+        // Config and tokenizer alone are not a model
+        fs::remove_file(dir.join("model-00001.safetensors")).unwrap();
+        fs::remove_file(dir.join("model-00002.safetensors")).unwrap();
+        assert!(collect_import_files(&dir).is_err());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
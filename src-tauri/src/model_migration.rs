@@ -0,0 +1,496 @@
+use crate::model_store::{sha256_file, ModelStore};
+use serde::Serialize;
+use std::fs;
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use tauri::Manager;
+
+// Event carrying `MigrationProgress` while the models directory is migrated
+const MIGRATION_EVENT: &str = "models-migration-progress";
+// Directory in the destination for files that are still being copied, an
+// interrupted copy continues from there. Kept out of `blobs/` and `manifests/`
+// so Ollama never sees a half-copied file.
+const PARTIAL_DIR: &str = ".migrating";
+const COPY_BUFFER_SIZE: usize = 1024 * 1024;
+// Emit a progress event at least every this many copied bytes
+const PROGRESS_STEP: u64 = 64 * 1024 * 1024;
+
+static MIGRATION_RUNNING: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MigrationFileKind {
+    Blob,
+    Manifest,
+}
+
+/// A file to bring over, paths are relative to the store's `blobs/` or `manifests/`
+#[derive(Debug, Clone, Serialize)]
+pub struct MigrationFile {
+    pub kind: MigrationFileKind,
+    pub relative_path: PathBuf,
+    pub size: u64,
+    /// Expected `sha256:<hex>` of a blob
+    pub digest: Option<String>,
+}
+
+/// Everything the migration copies, blobs first so a copied manifest never points at a missing blob
+#[derive(Debug, Clone, Serialize)]
+pub struct MigrationPlan {
+    pub files: Vec<MigrationFile>,
+    pub total_bytes: u64,
+    /// Bytes the destination still needs, without what an earlier run already copied
+    pub required_bytes: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MigrationPhase {
+    Planning,
+    Copying,
+    Cleanup,
+    Done,
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MigrationProgress {
+    pub phase: MigrationPhase,
+    pub files_done: usize,
+    pub files_total: usize,
+    pub bytes_done: u64,
+    pub bytes_total: u64,
+    pub current_file: Option<String>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct MigrationReport {
+    pub source: String,
+    pub destination: String,
+    pub files_copied: usize,
+    /// Files that were already complete and verified at the destination
+    pub files_skipped: usize,
+    pub bytes_copied: u64,
+    /// Files removed from the old location
+    pub files_removed: usize,
+}
+
+/// Blobs referenced by a manifest, then all manifests of the source store
+pub fn plan_migration(source: &ModelStore, destination: &ModelStore) -> io::Result<MigrationPlan> {
+    let inventory = source.inventory()?;
+    let mut files = Vec::new();
+
+    for blob in inventory.blobs.iter().filter(|b| b.present) {
+        let path = PathBuf::from(&blob.path);
+        let file_name = path.file_name().map(PathBuf::from).unwrap_or_default();
+        files.push(MigrationFile {
+            kind: MigrationFileKind::Blob,
+            relative_path: file_name,
+            size: blob.size,
+            digest: Some(blob.digest.clone()),
+        });
+    }
+
+    let manifests_dir = source.manifests_dir();
+    for (_, path) in source.manifest_files()? {
+        files.push(MigrationFile {
+            kind: MigrationFileKind::Manifest,
+            relative_path: path.strip_prefix(&manifests_dir).unwrap_or(&path).to_path_buf(),
+            size: fs::metadata(&path)?.len(),
+            digest: None,
+        });
+    }
+
+    let mut required_bytes = 0;
+    for file in &files {
+        let target = store_path(destination, file);
+        let existing = match fs::metadata(&target) {
+            Ok(metadata) if metadata.len() == file.size => file.size,
+            _ => fs::metadata(partial_path(destination, file)).map(|m| m.len().min(file.size)).unwrap_or(0),
+        };
+        required_bytes += file.size - existing;
+    }
+
+    Ok(MigrationPlan {
+        total_bytes: files.iter().map(|f| f.size).sum(),
+        required_bytes,
+        files,
+    })
+}
+
+/// Copy every planned file and verify it at the destination
+///
+/// A blob must hash to its digest, a manifest must match the source byte for
+/// byte. Files that are already complete are verified and skipped, and a file
+/// whose copy was interrupted continues from its part in `.migrating/`.
+pub fn copy_store(
+    source: &ModelStore,
+    destination: &ModelStore,
+    plan: &MigrationPlan,
+    on_progress: &mut dyn FnMut(&MigrationProgress),
+) -> io::Result<MigrationReport> {
+    let mut report = MigrationReport {
+        source: source.root().to_string_lossy().to_string(),
+        destination: destination.root().to_string_lossy().to_string(),
+        ..Default::default()
+    };
+    let mut progress = MigrationProgress {
+        phase: MigrationPhase::Copying,
+        files_done: 0,
+        files_total: plan.files.len(),
+        bytes_done: 0,
+        bytes_total: plan.total_bytes,
+        current_file: None,
+        error: None,
+    };
+
+    for file in &plan.files {
+        let from = store_path(source, file);
+        let to = store_path(destination, file);
+        let start_bytes = progress.bytes_done;
+        progress.current_file = Some(file.relative_path.to_string_lossy().to_string());
+        on_progress(&progress);
+
+        if is_complete(file, &from, &to)? {
+            report.files_skipped += 1;
+        } else {
+            let partial = partial_path(destination, file);
+            for dir in [to.parent(), partial.parent()].into_iter().flatten() {
+                fs::create_dir_all(dir)?;
+            }
+            let copied = copy_resumable(&from, &partial, &mut |copied| {
+                progress.bytes_done = start_bytes + copied;
+                on_progress(&progress);
+            })?;
+            verify_copy(file, &from, &partial)?;
+            fs::rename(&partial, &to)?;
+            report.files_copied += 1;
+            report.bytes_copied += copied;
+        }
+
+        progress.files_done += 1;
+        progress.bytes_done = start_bytes + file.size;
+    }
+
+    let _ = fs::remove_dir_all(destination.root().join(PARTIAL_DIR));
+    progress.current_file = None;
+    on_progress(&progress);
+    Ok(report)
+}
+
+/// Remove the migrated files from the old location, returns the number of removed files
+pub fn remove_migrated_files(source: &ModelStore, plan: &MigrationPlan) -> io::Result<usize> {
+    let mut removed = 0;
+    for file in &plan.files {
+        match fs::remove_file(store_path(source, file)) {
+            Ok(()) => removed += 1,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e),
+        }
+    }
+    remove_empty_dirs(&source.manifests_dir())?;
+    Ok(removed)
+}
+
+// Where `file` lives in `store`, the same relative layout in both stores
+fn store_path(store: &ModelStore, file: &MigrationFile) -> PathBuf {
+    match file.kind {
+        MigrationFileKind::Blob => store.blobs_dir().join(&file.relative_path),
+        MigrationFileKind::Manifest => store.manifests_dir().join(&file.relative_path),
+    }
+}
+
+fn partial_path(store: &ModelStore, file: &MigrationFile) -> PathBuf {
+    let kind = match file.kind {
+        MigrationFileKind::Blob => "blobs",
+        MigrationFileKind::Manifest => "manifests",
+    };
+    store.root().join(PARTIAL_DIR).join(kind).join(&file.relative_path)
+}
+
+// Whether `to` already holds a verified copy of `from`
+fn is_complete(file: &MigrationFile, from: &Path, to: &Path) -> io::Result<bool> {
+    match fs::metadata(to) {
+        Ok(metadata) if metadata.len() == file.size => Ok(verify_copy(file, from, to).is_ok()),
+        Ok(_) => Ok(false),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(false),
+        Err(e) => Err(e),
+    }
+}
+
+fn verify_copy(file: &MigrationFile, from: &Path, copy: &Path) -> io::Result<()> {
+    let matches = match &file.digest {
+        Some(digest) => sha256_file(copy)? == *digest,
+        None => fs::read(from)? == fs::read(copy)?,
+    };
+    if matches {
+        Ok(())
+    } else {
+        Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("Checksum mismatch after copying {}", from.display()),
+        ))
+    }
+}
+
+// Copy into `partial`, continuing after the bytes a previous attempt left there
+fn copy_resumable(from: &Path, partial: &Path, on_copied: &mut dyn FnMut(u64)) -> io::Result<u64> {
+    let mut source = fs::File::open(from)?;
+    let source_len = source.metadata()?.len();
+    let mut target = fs::OpenOptions::new().create(true).append(true).open(partial)?;
+
+    let mut offset = target.metadata()?.len();
+    if offset > source_len {
+        // Not a prefix of this file, start over
+        target.set_len(0)?;
+        offset = 0;
+    }
+    source.seek(SeekFrom::Start(offset))?;
+
+    let mut buffer = vec![0u8; COPY_BUFFER_SIZE];
+    let mut copied = offset;
+    let mut reported = offset;
+    loop {
+        let read = source.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        target.write_all(&buffer[..read])?;
+        copied += read as u64;
+        if copied - reported >= PROGRESS_STEP {
+            reported = copied;
+            on_copied(copied);
+        }
+    }
+    target.sync_all()?;
+    on_copied(copied);
+    Ok(copied - offset)
+}
+
+fn remove_empty_dirs(dir: &Path) -> io::Result<()> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(e),
+    };
+    for entry in entries {
+        let entry = entry?;
+        if entry.file_type()?.is_dir() {
+            remove_empty_dirs(&entry.path())?;
+            // Fails while the directory still has files, which is fine
+            let _ = fs::remove_dir(entry.path());
+        }
+    }
+    Ok(())
+}
+
+// Clears the running flag however the migration ends
+struct MigrationGuard;
+
+impl Drop for MigrationGuard {
+    fn drop(&mut self) {
+        MIGRATION_RUNNING.store(false, Ordering::SeqCst);
+    }
+}
+
+fn run_migration(new_path: &str, cleanup: bool, app_handle: &tauri::AppHandle) -> Result<MigrationReport, String> {
+    let source = ModelStore::from_config()?;
+    let destination = ModelStore::new(new_path);
+    let same_location = match (fs::canonicalize(source.root()), fs::canonicalize(destination.root())) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => source.root() == destination.root(),
+    };
+    if same_location {
+        return Err("The new models path is the current one".to_string());
+    }
+
+    let emit = |progress: &MigrationProgress| {
+        let _ = app_handle.emit_all(MIGRATION_EVENT, progress);
+    };
+    let mut progress = MigrationProgress {
+        phase: MigrationPhase::Planning,
+        files_done: 0,
+        files_total: 0,
+        bytes_done: 0,
+        bytes_total: 0,
+        current_file: None,
+        error: None,
+    };
+    emit(&progress);
+
+    let plan = plan_migration(&source, &destination)
+        .map_err(|e| format!("Failed to read the current models directory: {}", e))?;
+    fs::create_dir_all(destination.root())
+        .map_err(|e| format!("Failed to create {}: {}", destination.root().display(), e))?;
+
+    match crate::system_monitor::get_available_space_for_path(&destination.root().to_string_lossy()) {
+        Some(available) if available < plan.required_bytes => {
+            return Err(format!(
+                "Not enough free space at {}: {:.2} GB needed, {:.2} GB available",
+                new_path,
+                plan.required_bytes as f64 / 1_073_741_824.0,
+                available as f64 / 1_073_741_824.0,
+            ));
+        }
+        Some(_) => {}
+        None => println!("[MIGRATE] Could not determine free space at {}, copying anyway", new_path),
+    }
+
+    println!("[MIGRATE] {} -> {}: {} files, {} bytes ({} bytes to copy)",
+        source.root().display(), destination.root().display(), plan.files.len(), plan.total_bytes, plan.required_bytes);
+    let mut report = copy_store(&source, &destination, &plan, &mut |p| emit(p))
+        .map_err(|e| format!("Failed to copy models: {}", e))?;
+
+    // Ollama only sees the copies once it uses the new path
+    crate::config_manager::set_ollama_models_path(new_path.to_string())?;
+
+    if cleanup {
+        progress.phase = MigrationPhase::Cleanup;
+        progress.files_total = plan.files.len();
+        emit(&progress);
+        report.files_removed = remove_migrated_files(&source, &plan)
+            .map_err(|e| format!("Models were migrated, but cleaning up {} failed: {}", source.root().display(), e))?;
+    }
+
+    progress.phase = MigrationPhase::Done;
+    progress.files_done = plan.files.len();
+    progress.files_total = plan.files.len();
+    progress.bytes_done = plan.total_bytes;
+    progress.bytes_total = plan.total_bytes;
+    emit(&progress);
+    Ok(report)
+}
+
+/// Tauri command: Move the models to a new models path and switch the configuration to it
+///
+/// Manifests and blobs are copied and checksum-verified first, running it again
+/// after an interruption continues where it stopped. With `cleanup` the old
+/// copies are removed once the new path is configured.
+#[tauri::command]
+pub async fn migrate_models_dir(new_path: String, cleanup: bool, app_handle: tauri::AppHandle) -> Result<MigrationReport, String> {
+    if crate::ollama_api::has_active_downloads() {
+        return Err("Cannot migrate the models directory while downloads are running".to_string());
+    }
+    if MIGRATION_RUNNING.swap(true, Ordering::SeqCst) {
+        return Err("A models directory migration is already running".to_string());
+    }
+    let guard = MigrationGuard;
+
+    let handle = app_handle.clone();
+    let result = tokio::task::spawn_blocking(move || {
+        let _guard = guard;
+        run_migration(&new_path, cleanup, &handle)
+    })
+    .await
+    .map_err(|e| format!("Migration task failed: {}", e))?;
+
+    if let Err(error) = &result {
+        println!("[MIGRATE] Migration failed: {}", error);
+        let _ = app_handle.emit_all(MIGRATION_EVENT, MigrationProgress {
+            phase: MigrationPhase::Failed,
+            files_done: 0,
+            files_total: 0,
+            bytes_done: 0,
+            bytes_total: 0,
+            current_file: None,
+            error: Some(error.clone()),
+        });
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model_store::{Manifest, ManifestLayer, ModelRef};
+    use sha2::{Digest, Sha256};
+
+    fn write_model(store: &ModelStore, model: &str, blobs: &[&[u8]]) {
+        let layers: Vec<ManifestLayer> = blobs
+            .iter()
+            .map(|data| ManifestLayer {
+                media_type: "application/vnd.ollama.image.model".to_string(),
+                digest: format!("sha256:{:x}", Sha256::digest(data)),
+                size: data.len() as u64,
+            })
+            .collect();
+        fs::create_dir_all(store.blobs_dir()).unwrap();
+        for (layer, data) in layers.iter().zip(blobs) {
+            fs::write(store.blob_path(&layer.digest), data).unwrap();
+        }
+        let manifest = Manifest {
+            schema_version: 2,
+            media_type: String::new(),
+            config: layers[0].clone(),
+            layers: layers[1..].to_vec(),
+        };
+        let path = store.manifest_path(&ModelRef::parse(model).unwrap());
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, serde_json::to_vec(&manifest).unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_migration_resumes_verifies_and_cleans_up() {
+        let base = std::env::temp_dir().join(format!("ollama-pro-migrate-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&base);
+        let source = ModelStore::new(base.join("old"));
+        let destination = ModelStore::new(base.join("new"));
+        write_model(&source, "a:1b", &[b"{}", b"weights of a"]);
+        write_model(&source, "b:1b", &[b"{}", b"weights of b"]);
+
+        let plan = plan_migration(&source, &destination).unwrap();
+        assert_eq!(plan.files.len(), 5);
+        assert_eq!(plan.files.last().unwrap().kind, MigrationFileKind::Manifest);
+
+        // An earlier run copied the first blob and half of the second one
+        fs::create_dir_all(destination.blobs_dir()).unwrap();
+        let first = &plan.files[0];
+        fs::copy(store_path(&source, first), store_path(&destination, first)).unwrap();
+        let second = &plan.files[1];
+        let data = fs::read(store_path(&source, second)).unwrap();
+        let partial = partial_path(&destination, second);
+        fs::create_dir_all(partial.parent().unwrap()).unwrap();
+        fs::write(partial, &data[..4]).unwrap();
+
+        let plan = plan_migration(&source, &destination).unwrap();
+        assert_eq!(plan.required_bytes, plan.total_bytes - first.size - 4);
+
+        let mut events = Vec::new();
+        let report = copy_store(&source, &destination, &plan, &mut |p| events.push(p.bytes_done)).unwrap();
+        assert_eq!(report.files_skipped, 1);
+        assert_eq!(report.files_copied, 4);
+        assert_eq!(report.bytes_copied, plan.required_bytes);
+        assert_eq!(events.last(), Some(&plan.total_bytes));
+        assert!(destination.verify("a:1b").unwrap().valid);
+        assert!(destination.verify("b:1b").unwrap().valid);
+
+        assert_eq!(remove_migrated_files(&source, &plan).unwrap(), 5);
+        assert!(source.manifest_files().unwrap().is_empty());
+        assert!(!source.manifests_dir().join("registry.ollama.ai").exists());
+
+        let _ = fs::remove_dir_all(&base);
+    }
+
+    #[test]
+    fn test_corrupt_copy_is_rejected() {
+        let base = std::env::temp_dir().join(format!("ollama-pro-migrate-corrupt-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&base);
+        let source = ModelStore::new(base.join("old"));
+        let destination = ModelStore::new(base.join("new"));
+        write_model(&source, "a:1b", &[b"{}", b"weights"]);
+
+        // Same size, wrong content: the blob no longer matches its digest
+        let plan = plan_migration(&source, &destination).unwrap();
+        let blob = plan.files.iter().find(|f| f.size == 7).unwrap();
+        fs::write(store_path(&source, blob), b"WEIGHTS").unwrap();
+
+        let error = copy_store(&source, &destination, &plan, &mut |_| {}).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        assert!(!store_path(&destination, blob).exists());
+        assert!(partial_path(&destination, blob).exists());
+
+        let _ = fs::remove_dir_all(&base);
+    }
+}
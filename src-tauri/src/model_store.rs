@@ -0,0 +1,740 @@
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+const DEFAULT_REGISTRY: &str = "registry.ollama.ai";
+const DEFAULT_NAMESPACE: &str = "library";
+const DEFAULT_TAG: &str = "latest";
+// Read buffer used while hashing blobs
+const HASH_BUFFER_SIZE: usize = 1024 * 1024;
+
+/// Fully qualified model name as Ollama lays it out under `manifests/`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModelRef {
+    pub registry: String,
+    pub namespace: String,
+    pub name: String,
+    pub tag: String,
+}
+
+impl ModelRef {
+    /// Parse `[registry/][namespace/]name[:tag]`, filling in Ollama's defaults
+    pub fn parse(model: &str) -> Result<Self, String> {
+        let model = model.trim();
+        // A ':' before the last '/' belongs to a registry port, not to the tag
+        let (path, tag) = match model.rfind(':') {
+            Some(index) if !model[index..].contains('/') => (&model[..index], &model[index + 1..]),
+            _ => (model, DEFAULT_TAG),
+        };
+
+        let parts: Vec<&str> = path.split('/').collect();
+        if tag.is_empty() || parts.iter().any(|p| p.is_empty() || *p == "." || *p == "..") {
+            return Err(format!("Invalid model name {:?}", model));
+        }
+        let (registry, namespace, name) = match parts.as_slice() {
+            [name] => (DEFAULT_REGISTRY, DEFAULT_NAMESPACE, *name),
+            [namespace, name] => (DEFAULT_REGISTRY, *namespace, *name),
+            [registry, namespace, name] => (*registry, *namespace, *name),
+            _ => return Err(format!("Invalid model name {:?}", model)),
+        };
+
+        Ok(Self {
+            registry: registry.to_string(),
+            namespace: namespace.to_string(),
+            name: name.to_string(),
+            tag: tag.to_string(),
+        })
+    }
+}
+
+// Shortest name that parses back to the same model, the way `ollama list` shows it
+impl fmt::Display for ModelRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.registry != DEFAULT_REGISTRY {
+            write!(f, "{}/{}/{}:{}", self.registry, self.namespace, self.name, self.tag)
+        } else if self.namespace != DEFAULT_NAMESPACE {
+            write!(f, "{}/{}:{}", self.namespace, self.name, self.tag)
+        } else {
+            write!(f, "{}:{}", self.name, self.tag)
+        }
+    }
+}
+
+/// Layer or config entry of a manifest
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ManifestLayer {
+    pub media_type: String,
+    pub digest: String,
+    pub size: u64,
+}
+
+/// Model manifest as stored under `manifests/`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Manifest {
+    pub schema_version: u32,
+    #[serde(default)]
+    pub media_type: String,
+    pub config: ManifestLayer,
+    pub layers: Vec<ManifestLayer>,
+}
+
+impl Manifest {
+    /// Config blob followed by the layers
+    pub fn blobs(&self) -> impl Iterator<Item = &ManifestLayer> {
+        std::iter::once(&self.config).chain(self.layers.iter())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BlobStatus {
+    Ok,
+    Missing,
+    /// The file size differs from the manifest, the blob was not hashed
+    SizeMismatch,
+    /// The SHA-256 of the file differs from its digest
+    Corrupt,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BlobCheck {
+    pub digest: String,
+    pub media_type: String,
+    pub size: u64,
+    pub status: BlobStatus,
+    /// Size of the file on disk, if it exists
+    pub actual_size: Option<u64>,
+    /// Digest computed from the file, only for corrupt blobs
+    pub actual_digest: Option<String>,
+}
+
+/// Result of checking every blob of a model against its manifest
+#[derive(Debug, Clone, Serialize)]
+pub struct ModelVerification {
+    pub model: String,
+    pub manifest_path: String,
+    pub valid: bool,
+    pub blobs: Vec<BlobCheck>,
+}
+
+impl ModelVerification {
+    /// Blobs that are missing or don't match their digest
+    pub fn problems(&self) -> impl Iterator<Item = &BlobCheck> {
+        self.blobs.iter().filter(|b| b.status != BlobStatus::Ok)
+    }
+}
+
+/// A manifest found under `manifests/`
+#[derive(Debug, Clone, Serialize)]
+pub struct StoredModel {
+    pub name: String,
+    pub manifest_path: String,
+    /// Digests of the config blob and the layers
+    pub blobs: Vec<String>,
+    /// Size of all blobs of the model
+    pub size: u64,
+    /// Size of the blobs no other model references, freed when the model is deleted
+    pub unique_size: u64,
+}
+
+/// A blob file, or a blob a manifest references but that is not on disk
+#[derive(Debug, Clone, Serialize)]
+pub struct StoredBlob {
+    pub digest: String,
+    pub path: String,
+    /// Size on disk, or the manifest size if the file is missing
+    pub size: u64,
+    pub present: bool,
+    /// Models whose manifest references the blob
+    pub referenced_by: Vec<String>,
+}
+
+impl StoredBlob {
+    pub fn is_shared(&self) -> bool {
+        self.referenced_by.len() > 1
+    }
+}
+
+/// What is physically stored in the models directory
+#[derive(Debug, Clone, Serialize)]
+pub struct StoreInventory {
+    pub models_dir: String,
+    pub models: Vec<StoredModel>,
+    /// Blobs referenced by at least one manifest
+    pub blobs: Vec<StoredBlob>,
+    /// Blob files no manifest references
+    pub orphaned_blobs: Vec<StoredBlob>,
+    /// Manifest files that could not be read or parsed
+    pub invalid_manifests: Vec<String>,
+    /// Size of all blob files on disk
+    pub total_size: u64,
+    pub shared_size: u64,
+    pub orphaned_size: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GcReason {
+    /// Complete blob that no manifest references
+    Orphaned,
+    /// Leftover of an interrupted download
+    StalePartial,
+}
+
+/// A file `gc` would delete
+#[derive(Debug, Clone, Serialize)]
+pub struct GcCandidate {
+    pub path: String,
+    pub size: u64,
+    pub reason: GcReason,
+}
+
+/// Outcome of a garbage collection run, or of a dry run when `dry_run` is set
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct GcReport {
+    pub dry_run: bool,
+    pub candidates: Vec<GcCandidate>,
+    pub reclaimable_bytes: u64,
+    pub removed: Vec<String>,
+    pub freed_bytes: u64,
+    /// Files that could not be deleted, with the reason
+    pub errors: Vec<String>,
+}
+
+/// The `manifests/` and `blobs/` directories of a local Ollama installation
+#[derive(Debug, Clone)]
+pub struct ModelStore {
+    root: PathBuf,
+}
+
+impl ModelStore {
+    /// Store for a configured models path
+    ///
+    /// The path is usually the base `.ollama` directory, but `OLLAMA_MODELS`
+    /// may also point straight at the `models` directory.
+    pub fn new(models_path: impl AsRef<Path>) -> Self {
+        let base = models_path.as_ref();
+        let nested = base.join("models");
+        let root = if !base.join("manifests").is_dir() && (nested.is_dir() || !base.join("blobs").is_dir()) {
+            nested
+        } else {
+            base.to_path_buf()
+        };
+        Self { root }
+    }
+
+    /// Store for the models path from the app configuration
+    pub fn from_config() -> Result<Self, String> {
+        Ok(Self::new(crate::config_manager::get_ollama_models_path()?))
+    }
+
+    /// Store for the models path of a server profile, or as `from_config` when `None`
+    pub fn from_profile(profile: Option<&str>) -> Result<Self, String> {
+        Ok(Self::new(crate::config_manager::resolve_server(profile)?.models_path))
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn manifests_dir(&self) -> PathBuf {
+        self.root.join("manifests")
+    }
+
+    pub fn blobs_dir(&self) -> PathBuf {
+        self.root.join("blobs")
+    }
+
+    pub fn manifest_path(&self, model: &ModelRef) -> PathBuf {
+        self.manifests_dir()
+            .join(&model.registry)
+            .join(&model.namespace)
+            .join(&model.name)
+            .join(&model.tag)
+    }
+
+    /// Blob file for `sha256:<hex>`, Ollama names it `sha256-<hex>` (older versions kept the ':')
+    pub fn blob_path(&self, digest: &str) -> PathBuf {
+        let path = self.blobs_dir().join(digest.replacen(':', "-", 1));
+        if !path.exists() {
+            let legacy = self.blobs_dir().join(digest);
+            if legacy.exists() {
+                return legacy;
+            }
+        }
+        path
+    }
+
+    pub fn read_manifest(&self, model: &ModelRef) -> io::Result<Manifest> {
+        let content = fs::read(self.manifest_path(model))?;
+        serde_json::from_slice(&content).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    /// All manifests as `(model, path)`, laid out as `<registry>/<namespace>/<model>/<tag>`
+    pub fn manifest_files(&self) -> io::Result<Vec<(ModelRef, PathBuf)>> {
+        let mut files = Vec::new();
+        let root = self.manifests_dir();
+        if root.is_dir() {
+            collect_files(&root, &mut files)?;
+        }
+
+        let mut manifests = Vec::new();
+        for path in files {
+            let parts: Vec<String> = path.strip_prefix(&root)
+                .unwrap_or(&path)
+                .iter()
+                .map(|p| p.to_string_lossy().to_string())
+                .collect();
+            // Anything else (e.g. a stray .DS_Store) is not a manifest
+            if let [registry, namespace, name, tag] = parts.as_slice() {
+                let model = ModelRef {
+                    registry: registry.clone(),
+                    namespace: namespace.clone(),
+                    name: name.clone(),
+                    tag: tag.clone(),
+                };
+                manifests.push((model, path));
+            }
+        }
+        manifests.sort_by_key(|(model, _)| model.to_string());
+        Ok(manifests)
+    }
+
+    /// Blob files as `(digest, path)`, without partial downloads
+    pub fn blob_files(&self) -> io::Result<Vec<(String, PathBuf)>> {
+        let mut blobs = Vec::new();
+        let dir = match fs::read_dir(self.blobs_dir()) {
+            Ok(dir) => dir,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(blobs),
+            Err(e) => return Err(e),
+        };
+        for entry in dir {
+            let entry = entry?;
+            if let Some(digest) = blob_digest(&entry.file_name().to_string_lossy()) {
+                blobs.push((digest, entry.path()));
+            }
+        }
+        blobs.sort();
+        Ok(blobs)
+    }
+
+    /// Map every manifest to its blobs and find shared and orphaned blobs
+    pub fn inventory(&self) -> io::Result<StoreInventory> {
+        let mut models = Vec::new();
+        let mut invalid_manifests = Vec::new();
+        // digest -> (manifest size, referencing models)
+        let mut references: BTreeMap<String, (u64, Vec<String>)> = BTreeMap::new();
+
+        for (model, path) in self.manifest_files()? {
+            let manifest: Manifest = match fs::read(&path)
+                .ok()
+                .and_then(|content| serde_json::from_slice(&content).ok())
+            {
+                Some(manifest) => manifest,
+                None => {
+                    invalid_manifests.push(path.to_string_lossy().to_string());
+                    continue;
+                }
+            };
+
+            let name = model.to_string();
+            let mut digests = BTreeSet::new();
+            for layer in manifest.blobs() {
+                // A model can list the same blob twice, count it once
+                if digests.insert(layer.digest.clone()) {
+                    let entry = references.entry(layer.digest.clone()).or_insert((layer.size, Vec::new()));
+                    entry.1.push(name.clone());
+                }
+            }
+            models.push(StoredModel {
+                name,
+                manifest_path: path.to_string_lossy().to_string(),
+                blobs: digests.into_iter().collect(),
+                size: 0,
+                unique_size: 0,
+            });
+        }
+
+        let on_disk: BTreeMap<String, PathBuf> = self.blob_files()?.into_iter().collect();
+        let mut blobs = Vec::new();
+        for (digest, (manifest_size, referenced_by)) in &references {
+            let path = on_disk.get(digest).cloned().unwrap_or_else(|| self.blob_path(digest));
+            let size = fs::metadata(&path).map(|m| m.len()).ok();
+            blobs.push(StoredBlob {
+                digest: digest.clone(),
+                path: path.to_string_lossy().to_string(),
+                size: size.unwrap_or(*manifest_size),
+                present: size.is_some(),
+                referenced_by: referenced_by.clone(),
+            });
+        }
+
+        let mut orphaned_blobs = Vec::new();
+        for (digest, path) in &on_disk {
+            if !references.contains_key(digest) {
+                orphaned_blobs.push(StoredBlob {
+                    digest: digest.clone(),
+                    path: path.to_string_lossy().to_string(),
+                    size: fs::metadata(path)?.len(),
+                    present: true,
+                    referenced_by: Vec::new(),
+                });
+            }
+        }
+
+        let sizes: BTreeMap<&str, &StoredBlob> = blobs.iter().map(|b| (b.digest.as_str(), b)).collect();
+        for model in &mut models {
+            for digest in &model.blobs {
+                let blob = sizes[digest.as_str()];
+                model.size += blob.size;
+                if !blob.is_shared() {
+                    model.unique_size += blob.size;
+                }
+            }
+        }
+
+        let present_size = |blobs: &[StoredBlob]| blobs.iter().filter(|b| b.present).map(|b| b.size).sum::<u64>();
+        let orphaned_size = present_size(&orphaned_blobs);
+        Ok(StoreInventory {
+            models_dir: self.root.to_string_lossy().to_string(),
+            shared_size: blobs.iter().filter(|b| b.present && b.is_shared()).map(|b| b.size).sum(),
+            total_size: present_size(&blobs) + orphaned_size,
+            orphaned_size,
+            models,
+            blobs,
+            orphaned_blobs,
+            invalid_manifests,
+        })
+    }
+
+    /// Orphaned blobs and partial downloads untouched for at least `partial_age`
+    ///
+    /// Fails if a manifest can't be parsed: its blobs would look orphaned.
+    pub fn gc_candidates(&self, partial_age: Duration, now: SystemTime) -> io::Result<Vec<GcCandidate>> {
+        let inventory = self.inventory()?;
+        if let Some(path) = inventory.invalid_manifests.first() {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, format!("Unreadable manifest {}", path)));
+        }
+
+        let mut candidates: Vec<GcCandidate> = inventory.orphaned_blobs
+            .into_iter()
+            .map(|b| GcCandidate { path: b.path, size: b.size, reason: GcReason::Orphaned })
+            .collect();
+
+        let dir = match fs::read_dir(self.blobs_dir()) {
+            Ok(dir) => dir,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(candidates),
+            Err(e) => return Err(e),
+        };
+        for entry in dir {
+            let entry = entry?;
+            if !is_partial_blob(&entry.file_name().to_string_lossy()) {
+                continue;
+            }
+            let metadata = entry.metadata()?;
+            let idle = metadata.modified()
+                .ok()
+                .and_then(|modified| now.duration_since(modified).ok())
+                .unwrap_or_default();
+            if idle >= partial_age {
+                candidates.push(GcCandidate {
+                    path: entry.path().to_string_lossy().to_string(),
+                    size: metadata.len(),
+                    reason: GcReason::StalePartial,
+                });
+            }
+        }
+
+        candidates.sort_by(|a, b| a.path.cmp(&b.path));
+        Ok(candidates)
+    }
+
+    /// Find garbage and, unless `dry_run`, delete the candidates listed in `confirmed`
+    ///
+    /// Only files that are still candidates are deleted, so a confirmation based
+    /// on an older dry run can't remove a blob a new manifest references by now.
+    pub fn gc(&self, dry_run: bool, confirmed: &[String], partial_age: Duration) -> io::Result<GcReport> {
+        let candidates = self.gc_candidates(partial_age, SystemTime::now())?;
+        let mut report = GcReport {
+            dry_run,
+            reclaimable_bytes: candidates.iter().map(|c| c.size).sum(),
+            ..Default::default()
+        };
+
+        if !dry_run {
+            for candidate in candidates.iter().filter(|c| confirmed.contains(&c.path)) {
+                match fs::remove_file(&candidate.path) {
+                    Ok(()) => {
+                        report.freed_bytes += candidate.size;
+                        report.removed.push(candidate.path.clone());
+                    }
+                    Err(e) => report.errors.push(format!("{}: {}", candidate.path, e)),
+                }
+            }
+        }
+
+        report.candidates = candidates;
+        Ok(report)
+    }
+
+    /// Re-hash every blob referenced by the model's manifest
+    pub fn verify(&self, model: &str) -> io::Result<ModelVerification> {
+        let model_ref = ModelRef::parse(model).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+        let manifest = self.read_manifest(&model_ref)?;
+
+        let mut blobs = Vec::new();
+        for layer in manifest.blobs() {
+            blobs.push(self.check_blob(layer)?);
+        }
+
+        Ok(ModelVerification {
+            model: model.to_string(),
+            manifest_path: self.manifest_path(&model_ref).to_string_lossy().to_string(),
+            valid: blobs.iter().all(|b| b.status == BlobStatus::Ok),
+            blobs,
+        })
+    }
+
+    fn check_blob(&self, layer: &ManifestLayer) -> io::Result<BlobCheck> {
+        let mut check = BlobCheck {
+            digest: layer.digest.clone(),
+            media_type: layer.media_type.clone(),
+            size: layer.size,
+            status: BlobStatus::Ok,
+            actual_size: None,
+            actual_digest: None,
+        };
+
+        let path = self.blob_path(&layer.digest);
+        let metadata = match fs::metadata(&path) {
+            Ok(metadata) => metadata,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                check.status = BlobStatus::Missing;
+                return Ok(check);
+            }
+            Err(e) => return Err(e),
+        };
+
+        check.actual_size = Some(metadata.len());
+        if metadata.len() != layer.size {
+            // No need to hash a file that is already known to be wrong
+            check.status = BlobStatus::SizeMismatch;
+            return Ok(check);
+        }
+
+        let digest = sha256_file(&path)?;
+        if digest != layer.digest {
+            check.status = BlobStatus::Corrupt;
+            check.actual_digest = Some(digest);
+        }
+        Ok(check)
+    }
+}
+
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            collect_files(&entry.path(), files)?;
+        } else if file_type.is_file() {
+            files.push(entry.path());
+        }
+    }
+    Ok(())
+}
+
+/// Digest of a complete blob file name (`sha256-<hex>` or `sha256:<hex>`)
+pub fn blob_digest(file_name: &str) -> Option<String> {
+    let hex = file_name.strip_prefix("sha256-").or_else(|| file_name.strip_prefix("sha256:"))?;
+    if hex.len() == 64 && hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        Some(format!("sha256:{}", hex))
+    } else {
+        None
+    }
+}
+
+// `sha256-<hex>-partial` and its numbered chunk files
+fn is_partial_blob(file_name: &str) -> bool {
+    match (file_name.get(..71), file_name.get(71..)) {
+        (Some(blob), Some(suffix)) => blob_digest(blob).is_some() && suffix.starts_with("-partial"),
+        _ => false,
+    }
+}
+
+/// SHA-256 of a file in Ollama's `sha256:<hex>` digest format
+pub fn sha256_file(path: &Path) -> io::Result<String> {
+    let mut file = fs::File::open(path)?;
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; HASH_BUFFER_SIZE];
+    loop {
+        let read = file.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+    Ok(format!("sha256:{:x}", hasher.finalize()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digest_of(data: &[u8]) -> String {
+        format!("sha256:{:x}", Sha256::digest(data))
+    }
+
+    fn layer(data: &[u8]) -> ManifestLayer {
+        ManifestLayer {
+            media_type: "application/vnd.ollama.image.model".to_string(),
+            digest: digest_of(data),
+            size: data.len() as u64,
+        }
+    }
+
+    fn temp_store(name: &str) -> (PathBuf, ModelStore) {
+        let base = std::env::temp_dir().join(format!("ollama-pro-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&base);
+        let store = ModelStore::new(&base);
+        fs::create_dir_all(store.blobs_dir()).unwrap();
+        (base, store)
+    }
+
+    fn write_manifest(store: &ModelStore, model: &str, config: &ManifestLayer, layers: &[&ManifestLayer]) {
+        let manifest = Manifest {
+            schema_version: 2,
+            media_type: String::new(),
+            config: config.clone(),
+            layers: layers.iter().map(|l| (*l).clone()).collect(),
+        };
+        let path = store.manifest_path(&ModelRef::parse(model).unwrap());
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, serde_json::to_vec(&manifest).unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_parse_model_ref() {
+        let model = ModelRef::parse("llama3").unwrap();
+        assert_eq!((model.registry.as_str(), model.namespace.as_str(), model.name.as_str(), model.tag.as_str()),
+            ("registry.ollama.ai", "library", "llama3", "latest"));
+
+        let model = ModelRef::parse("user/model:7b").unwrap();
+        assert_eq!((model.namespace.as_str(), model.tag.as_str()), ("user", "7b"));
+
+        let model = ModelRef::parse("localhost:5000/team/model").unwrap();
+        assert_eq!((model.registry.as_str(), model.tag.as_str()), ("localhost:5000", "latest"));
+
+        assert!(ModelRef::parse("../etc/passwd").is_err());
+        assert!(ModelRef::parse("model:").is_err());
+
+        for name in ["llama3:latest", "user/model:7b", "localhost:5000/team/model:latest"] {
+            assert_eq!(ModelRef::parse(name).unwrap().to_string(), name);
+        }
+    }
+
+    #[test]
+    fn test_verify_reports_missing_and_corrupt_blobs() {
+        let (base, store) = temp_store("verify");
+        assert_eq!(store.manifests_dir(), base.join("models").join("manifests"));
+
+        let config = layer(b"{}");
+        let weights = layer(b"weights");
+        let template = layer(b"template");
+        write_manifest(&store, "test:1b", &config, &[&weights, &template]);
+        fs::write(store.blob_path(&config.digest), b"{}").unwrap();
+        // Same size, different content
+        fs::write(store.blob_path(&weights.digest), b"WEIGHTS").unwrap();
+
+        let result = store.verify("test:1b").unwrap();
+        let statuses: Vec<BlobStatus> = result.blobs.iter().map(|b| b.status).collect();
+        assert!(!result.valid);
+        assert_eq!(statuses, vec![BlobStatus::Ok, BlobStatus::Corrupt, BlobStatus::Missing]);
+        assert_eq!(result.blobs[1].actual_digest, Some(digest_of(b"WEIGHTS")));
+
+        fs::write(store.blob_path(&weights.digest), b"weights").unwrap();
+        fs::write(store.blob_path(&template.digest), b"template").unwrap();
+        assert!(store.verify("test:1b").unwrap().valid);
+        assert_eq!(store.verify("other").unwrap_err().kind(), io::ErrorKind::NotFound);
+
+        let _ = fs::remove_dir_all(&base);
+    }
+
+    #[test]
+    fn test_inventory_finds_shared_and_orphaned_blobs() {
+        let (base, store) = temp_store("inventory");
+
+        let config = layer(b"{}");
+        let weights = layer(b"shared weights");
+        let adapter = layer(b"adapter");
+        let orphan = layer(b"left over");
+        write_manifest(&store, "base:7b", &config, &[&weights]);
+        write_manifest(&store, "user/tuned:7b", &config, &[&weights, &adapter]);
+        for (blob, data) in [(&config, &b"{}"[..]), (&weights, b"shared weights"), (&adapter, b"adapter"), (&orphan, b"left over")] {
+            fs::write(store.blob_path(&blob.digest), data).unwrap();
+        }
+        // Partial downloads are not blobs yet
+        fs::write(store.blobs_dir().join(format!("{}-partial", orphan.digest.replace(':', "-"))), b"left").unwrap();
+        fs::write(store.manifests_dir().join("registry.ollama.ai/library/base/broken"), b"not json").unwrap();
+
+        let inventory = store.inventory().unwrap();
+        let names: Vec<&str> = inventory.models.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(names, vec!["base:7b", "user/tuned:7b"]);
+        assert_eq!(inventory.invalid_manifests.len(), 1);
+
+        // Deleting the base model frees nothing, everything it uses is shared
+        assert_eq!(inventory.models[0].size, config.size + weights.size);
+        assert_eq!(inventory.models[0].unique_size, 0);
+        assert_eq!(inventory.models[1].unique_size, adapter.size);
+        assert_eq!(inventory.shared_size, config.size + weights.size);
+
+        let orphans: Vec<&str> = inventory.orphaned_blobs.iter().map(|b| b.digest.as_str()).collect();
+        assert_eq!(orphans, vec![orphan.digest.as_str()]);
+        assert_eq!(inventory.orphaned_size, orphan.size);
+        assert_eq!(inventory.total_size, config.size + weights.size + adapter.size + orphan.size);
+
+        let _ = fs::remove_dir_all(&base);
+    }
+
+    #[test]
+    fn test_gc_removes_only_confirmed_candidates() {
+        let (base, store) = temp_store("gc");
+
+        let config = layer(b"{}");
+        let orphan = layer(b"left over");
+        write_manifest(&store, "base:7b", &config, &[]);
+        fs::write(store.blob_path(&config.digest), b"{}").unwrap();
+        fs::write(store.blob_path(&orphan.digest), b"left over").unwrap();
+        let partial = store.blobs_dir().join(format!("{}-partial-0", orphan.digest.replace(':', "-")));
+        fs::write(&partial, b"left").unwrap();
+
+        // A fresh partial file may still belong to a paused download
+        let later = SystemTime::now() + Duration::from_secs(3600);
+        assert_eq!(store.gc_candidates(Duration::from_secs(7200), later).unwrap().len(), 1);
+        let reasons: Vec<GcReason> = store.gc_candidates(Duration::from_secs(60), later).unwrap()
+            .into_iter()
+            .map(|c| c.reason)
+            .collect();
+        assert_eq!(reasons, vec![GcReason::Orphaned, GcReason::StalePartial]);
+
+        let report = store.gc(true, &[], Duration::ZERO).unwrap();
+        assert_eq!(report.reclaimable_bytes, orphan.size + 4);
+        assert!(report.removed.is_empty());
+
+        let orphan_path = store.blob_path(&orphan.digest).to_string_lossy().to_string();
+        let report = store.gc(false, std::slice::from_ref(&orphan_path), Duration::ZERO).unwrap();
+        assert_eq!(report.removed, vec![orphan_path]);
+        assert_eq!(report.freed_bytes, orphan.size);
+        assert!(partial.exists());
+        assert!(store.blob_path(&config.digest).exists());
+
+        let _ = fs::remove_dir_all(&base);
+    }
+}
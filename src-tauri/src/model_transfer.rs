@@ -0,0 +1,270 @@
+use crate::config_manager::{resolve_server, ServerConnection};
+use crate::model_import::{hash_file, upload_blob, ImportFile};
+use crate::model_store::{Manifest, ModelRef, ModelStore};
+use crate::ollama_api::{forward_status_stream, register_generation};
+use crate::ollama_client::OllamaClient;
+use crate::ollama_error::OllamaError;
+use serde_json::{json, Map, Value};
+use std::fs;
+
+const MEDIA_MODEL: &str = "application/vnd.ollama.image.model";
+const MEDIA_PROJECTOR: &str = "application/vnd.ollama.image.projector";
+const MEDIA_ADAPTER: &str = "application/vnd.ollama.image.adapter";
+const MEDIA_TEMPLATE: &str = "application/vnd.ollama.image.template";
+const MEDIA_SYSTEM: &str = "application/vnd.ollama.image.system";
+const MEDIA_PARAMS: &str = "application/vnd.ollama.image.params";
+const MEDIA_LICENSE: &str = "application/vnd.ollama.image.license";
+const MEDIA_MESSAGES: &str = "application/vnd.ollama.image.messages";
+
+/// A weights, projector or adapter blob that has to exist on the target
+#[derive(Debug, Clone, PartialEq)]
+pub struct TransferBlob {
+    pub file: ImportFile,
+    pub digest: String,
+}
+
+/// Blobs to upload and the `/api/create` request that recreates the model from them
+#[derive(Debug, Clone)]
+pub struct TransferPlan {
+    pub blobs: Vec<TransferBlob>,
+    pub payload: Value,
+}
+
+// File name for the n-th blob of a kind in the `files` or `adapters` map
+fn blob_name(prefix: &str, index: usize) -> String {
+    if index == 0 {
+        format!("{}.gguf", prefix)
+    } else {
+        format!("{}-{}.gguf", prefix, index + 1)
+    }
+}
+
+/// Plan the recreation of a stored model as `target_name`
+///
+/// Weights, projectors and adapters become blob uploads; template, system
+/// prompt, parameters, license and messages are read from the store and sent
+/// as fields of the create request.
+pub fn plan_transfer(store: &ModelStore, manifest: &Manifest, target_name: &str) -> Result<TransferPlan, String> {
+    let mut blobs: Vec<TransferBlob> = Vec::new();
+    let mut files = Map::new();
+    let mut adapters = Map::new();
+    let mut licenses = Vec::new();
+    let mut payload = json!({ "model": target_name });
+
+    let read_text = |digest: &str| {
+        fs::read_to_string(store.blob_path(digest)).map_err(|e| format!("Failed to read blob {}: {}", digest, e))
+    };
+    let read_json = |digest: &str| -> Result<Value, String> {
+        serde_json::from_str(&read_text(digest)?).map_err(|e| format!("Invalid JSON in blob {}: {}", digest, e))
+    };
+
+    for layer in &manifest.layers {
+        let digest = layer.digest.as_str();
+        let (map, prefix) = match layer.media_type.as_str() {
+            MEDIA_MODEL => (&mut files, "model"),
+            MEDIA_PROJECTOR => (&mut files, "projector"),
+            MEDIA_ADAPTER => (&mut adapters, "adapter"),
+            MEDIA_TEMPLATE => {
+                payload["template"] = json!(read_text(digest)?);
+                continue;
+            }
+            MEDIA_SYSTEM => {
+                payload["system"] = json!(read_text(digest)?);
+                continue;
+            }
+            MEDIA_PARAMS => {
+                payload["parameters"] = read_json(digest)?;
+                continue;
+            }
+            MEDIA_MESSAGES => {
+                payload["messages"] = read_json(digest)?;
+                continue;
+            }
+            MEDIA_LICENSE => {
+                licenses.push(read_text(digest)?);
+                continue;
+            }
+            other => {
+                println!("[TRANSFER] Skipping layer with unknown media type: {}, digest={}", other, digest);
+                continue;
+            }
+        };
+
+        let index = blobs.iter().filter(|b| b.file.name.starts_with(prefix)).count();
+        let path = store.blob_path(digest);
+        if !path.is_file() {
+            return Err(format!("Blob {} is missing from {}", digest, store.root().display()));
+        }
+
+        let name = blob_name(prefix, index);
+        map.insert(name.clone(), json!(digest));
+        blobs.push(TransferBlob {
+            file: ImportFile { name, path, size: layer.size },
+            digest: digest.to_string(),
+        });
+    }
+
+    if files.is_empty() {
+        return Err("The manifest has no model weights".to_string());
+    }
+    payload["files"] = Value::Object(files);
+    if !adapters.is_empty() {
+        payload["adapters"] = Value::Object(adapters);
+    }
+    if !licenses.is_empty() {
+        payload["license"] = json!(licenses);
+    }
+    Ok(TransferPlan { blobs, payload })
+}
+
+// Upload the blobs the target lacks, checking each against its digest first, then create the model
+async fn run_transfer(client: &OllamaClient, plan: &TransferPlan, channel_id: &str, app_handle: &tauri::AppHandle) -> Result<(), OllamaError> {
+    for blob in &plan.blobs {
+        // Blobs the target already has were verified when they were stored there
+        let on_server = client.blob_exists(&blob.digest).await?;
+        if !on_server {
+            let digest = hash_file(&blob.file, channel_id, app_handle).await?;
+            if digest != blob.digest {
+                return Err(OllamaError::Other(format!(
+                    "Blob {} in the source store is corrupt, its content hashes to {}", blob.digest, digest
+                )));
+            }
+        }
+        upload_blob(client, &blob.file, &blob.digest, on_server, channel_id, app_handle).await?;
+    }
+
+    let response = client.create_stream(&plan.payload).await?;
+    forward_status_stream(response, channel_id, app_handle).await
+}
+
+// The source's manifest and blobs are read from disk, so a remote source needs a configured models directory
+fn source_models_path(source: &ServerConnection) -> Result<&str, OllamaError> {
+    source.reachable_models_path().ok_or_else(|| OllamaError::Other(format!(
+        "{} is not on this machine, set the models path of its profile to a directory that shares its models",
+        source.host
+    )))
+}
+
+/// Tauri command: Copy a model from one server to another
+///
+/// The manifest and blobs are read from the source server's models directory,
+/// which has to be reachable from this machine: the source is either local or
+/// its profile sets a models path. `target_name` defaults to the
+/// source name. Progress goes to `channel_id`, which also works as the request
+/// id for `cancel_generation`.
+#[tauri::command]
+pub async fn transfer_model(
+    model_name: String,
+    source_profile: Option<String>,
+    target_profile: Option<String>,
+    target_name: Option<String>,
+    channel_id: String,
+    app_handle: tauri::AppHandle,
+) -> Result<String, OllamaError> {
+    let (mut cancel_rx, _guard) = register_generation(&channel_id)?;
+    let target_name = target_name.filter(|name| !name.is_empty()).unwrap_or_else(|| model_name.clone());
+    let source = resolve_server(source_profile.as_deref()).map_err(OllamaError::Other)?;
+    let target = resolve_server(target_profile.as_deref()).map_err(OllamaError::Other)?;
+    if source.host == target.host && target_name == model_name {
+        return Err(OllamaError::Other(format!("{} already is on {}", model_name, target.host)));
+    }
+
+    let store = ModelStore::new(source_models_path(&source)?);
+    let model = ModelRef::parse(&model_name).map_err(OllamaError::Other)?;
+    let manifest = store.read_manifest(&model).map_err(|e| match e.kind() {
+        std::io::ErrorKind::NotFound => OllamaError::ModelNotFound(format!(
+            "No manifest for {} in {}, the source models directory must be reachable from this machine",
+            model_name, store.root().display()
+        )),
+        _ => OllamaError::Other(format!("Failed to read manifest of {}: {}", model_name, e)),
+    })?;
+    let plan = plan_transfer(&store, &manifest, &target_name).map_err(OllamaError::Other)?;
+    let client = OllamaClient::from_server(&target)?;
+    println!("[TRANSFER] {} from {} to {} as {}: {} blob(s)", model_name, source.host, target.host, target_name, plan.blobs.len());
+
+    tokio::select! {
+        _ = &mut cancel_rx => {
+            // Dropping the future aborts the running upload or create request
+            println!("[TRANSFER] Transfer cancelled: channel_id={}", channel_id);
+            Err(OllamaError::Cancelled)
+        }
+        result = run_transfer(&client, &plan, &channel_id, &app_handle) => {
+            result?;
+            Ok(format!("Model {} transferred to {} as {}", model_name, target.host, target_name))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    fn write_blob(root: &Path, hex_char: char, content: &str) -> String {
+        let hex = hex_char.to_string().repeat(64);
+        fs::write(root.join("blobs").join(format!("sha256-{}", hex)), content).unwrap();
+        format!("sha256:{}", hex)
+    }
+
+    #[test]
+    fn test_plan_transfer() {
+        let root = std::env::temp_dir().join(format!("ollama-pro-transfer-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("blobs")).unwrap();
+        fs::create_dir_all(root.join("manifests")).unwrap();
+
+        let weights = write_blob(&root, 'a', "GGUF weights");
+        let adapter = write_blob(&root, 'b', "GGUF adapter");
+        let template = write_blob(&root, 'c', "{{ .Prompt }}");
+        let params = write_blob(&root, 'd', r#"{"temperature":0.2,"stop":["<|eot_id|>"]}"#);
+        let license = write_blob(&root, 'e', "MIT");
+        let layer = |media: &str, digest: &str| json!({ "mediaType": media, "digest": digest, "size": 12 });
+        let manifest: Manifest = serde_json::from_value(json!({
+            "schemaVersion": 2,
+            "config": layer("application/vnd.docker.container.image.v1+json", &license),
+            "layers": [
+                layer(MEDIA_MODEL, &weights),
+                layer(MEDIA_ADAPTER, &adapter),
+                layer(MEDIA_TEMPLATE, &template),
+                layer(MEDIA_PARAMS, &params),
+                layer(MEDIA_LICENSE, &license),
+            ],
+        }))
+        .unwrap();
+
+        let store = ModelStore::new(&root);
+        let plan = plan_transfer(&store, &manifest, "llama3.2:tuned").unwrap();
+        let names: Vec<&str> = plan.blobs.iter().map(|b| b.file.name.as_str()).collect();
+        assert_eq!(names, vec!["model.gguf", "adapter.gguf"]);
+        assert_eq!(plan.payload["files"]["model.gguf"], json!(weights));
+        assert_eq!(plan.payload["adapters"]["adapter.gguf"], json!(adapter));
+        assert_eq!(plan.payload["template"], json!("{{ .Prompt }}"));
+        assert_eq!(plan.payload["parameters"]["temperature"], json!(0.2));
+        assert_eq!(plan.payload["license"], json!(["MIT"]));
+        assert_eq!(plan.payload["model"], json!("llama3.2:tuned"));
+
+        // A missing weights blob stops the transfer before anything is uploaded
+        fs::remove_file(store.blob_path(&weights)).unwrap();
+        assert!(plan_transfer(&store, &manifest, "llama3.2:tuned").unwrap_err().contains("missing"));
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_source_models_path_needs_local_or_configured_path() {
+        let server = |host: &str, models_path_configured: bool| ServerConnection {
+            profile: Some("source".to_string()),
+            host: host.to_string(),
+            headers: Default::default(),
+            auth: Default::default(),
+            tls: Default::default(),
+            timeouts: Default::default(),
+            models_path: "/home/me/.ollama".to_string(),
+            models_path_configured,
+        };
+        assert_eq!(source_models_path(&server("http://127.0.0.1:11434", false)).unwrap(), "/home/me/.ollama");
+        assert_eq!(source_models_path(&server("http://10.0.0.5:11434", true)).unwrap(), "/home/me/.ollama");
+        // The local default would silently describe another machine's models
+        assert!(matches!(source_models_path(&server("http://10.0.0.5:11434", false)), Err(OllamaError::Other(_))));
+    }
+}
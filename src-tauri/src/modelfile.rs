@@ -0,0 +1,504 @@
+use serde::Serialize;
+use std::fmt;
+
+/// A Modelfile instruction with its typed value
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "directive", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum Directive {
+    From { model: String },
+    Parameter { name: String, value: String },
+    Template { template: String },
+    System { system: String },
+    Adapter { path: String },
+    License { license: String },
+    Message { role: MessageRole, content: String },
+    Requires { version: String },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MessageRole {
+    System,
+    User,
+    Assistant,
+}
+
+impl MessageRole {
+    fn parse(role: &str) -> Option<Self> {
+        match role.to_ascii_lowercase().as_str() {
+            "system" => Some(MessageRole::System),
+            "user" => Some(MessageRole::User),
+            "assistant" => Some(MessageRole::Assistant),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MessageRole::System => "system",
+            MessageRole::User => "user",
+            MessageRole::Assistant => "assistant",
+        }
+    }
+}
+
+/// One source element of a Modelfile
+///
+/// Parsed entries keep their exact source text in `raw`, so serializing an
+/// unchanged Modelfile gives back the input byte for byte. Entries without
+/// `raw` (built or edited in code) are written in canonical form.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Entry {
+    Directive {
+        #[serde(flatten)]
+        directive: Directive,
+        /// 1-based line the instruction starts on, 0 for entries built in code
+        line: usize,
+        #[serde(skip)]
+        raw: Option<String>,
+    },
+    Comment {
+        text: String,
+        line: usize,
+        #[serde(skip)]
+        raw: Option<String>,
+    },
+    Blank { raw: String, line: usize },
+    /// A line that could not be parsed, kept as is
+    Invalid { raw: String, line: usize },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/// A problem found while parsing or validating, tied to a 1-based line
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Diagnostic {
+    pub line: usize,
+    pub severity: Severity,
+    pub message: String,
+}
+
+impl Diagnostic {
+    fn error(line: usize, message: impl Into<String>) -> Self {
+        Self { line, severity: Severity::Error, message: message.into() }
+    }
+
+    fn warning(line: usize, message: impl Into<String>) -> Self {
+        Self { line, severity: Severity::Warning, message: message.into() }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct Modelfile {
+    pub entries: Vec<Entry>,
+}
+
+/// Parse result for the frontend editor
+#[derive(Debug, Clone, Serialize)]
+pub struct ModelfileAnalysis {
+    pub modelfile: Modelfile,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ParameterType {
+    Int,
+    Float,
+    Bool,
+    String,
+}
+
+// Options Ollama accepts in PARAMETER lines and the type of their value
+const KNOWN_PARAMETERS: &[(&str, ParameterType)] = &[
+    ("num_ctx", ParameterType::Int),
+    ("num_batch", ParameterType::Int),
+    ("num_gpu", ParameterType::Int),
+    ("main_gpu", ParameterType::Int),
+    ("num_thread", ParameterType::Int),
+    ("num_keep", ParameterType::Int),
+    ("num_predict", ParameterType::Int),
+    ("seed", ParameterType::Int),
+    ("top_k", ParameterType::Int),
+    ("repeat_last_n", ParameterType::Int),
+    ("mirostat", ParameterType::Int),
+    ("temperature", ParameterType::Float),
+    ("top_p", ParameterType::Float),
+    ("min_p", ParameterType::Float),
+    ("typical_p", ParameterType::Float),
+    ("tfs_z", ParameterType::Float),
+    ("repeat_penalty", ParameterType::Float),
+    ("presence_penalty", ParameterType::Float),
+    ("frequency_penalty", ParameterType::Float),
+    ("mirostat_tau", ParameterType::Float),
+    ("mirostat_eta", ParameterType::Float),
+    ("use_mmap", ParameterType::Bool),
+    ("use_mlock", ParameterType::Bool),
+    ("numa", ParameterType::Bool),
+    ("low_vram", ParameterType::Bool),
+    ("penalize_newline", ParameterType::Bool),
+    ("stop", ParameterType::String),
+];
+
+// Parameters that may be given more than once
+const REPEATABLE_PARAMETERS: &[&str] = &["stop"];
+
+impl Modelfile {
+    /// Parse a Modelfile, collecting every problem instead of stopping at the first one
+    pub fn parse(input: &str) -> (Self, Vec<Diagnostic>) {
+        let lines: Vec<&str> = input.split_inclusive('\n').collect();
+        let mut entries = Vec::new();
+        let mut diagnostics = Vec::new();
+        let mut index = 0;
+
+        while index < lines.len() {
+            let line_number = index + 1;
+            let text = lines[index].trim();
+
+            if text.is_empty() {
+                entries.push(Entry::Blank { raw: lines[index].to_string(), line: line_number });
+                index += 1;
+                continue;
+            }
+            if let Some(comment) = text.strip_prefix('#') {
+                entries.push(Entry::Comment {
+                    text: comment.trim().to_string(),
+                    line: line_number,
+                    raw: Some(lines[index].to_string()),
+                });
+                index += 1;
+                continue;
+            }
+
+            match parse_instruction(&lines, index) {
+                Ok((directive, consumed)) => {
+                    entries.push(Entry::Directive {
+                        directive,
+                        line: line_number,
+                        raw: Some(lines[index..index + consumed].concat()),
+                    });
+                    index += consumed;
+                }
+                Err((message, consumed)) => {
+                    diagnostics.push(Diagnostic::error(line_number, message));
+                    entries.push(Entry::Invalid { raw: lines[index..index + consumed].concat(), line: line_number });
+                    index += consumed;
+                }
+            }
+        }
+
+        let modelfile = Modelfile { entries };
+        diagnostics.extend(modelfile.validate());
+        diagnostics.sort_by_key(|d| d.line);
+        (modelfile, diagnostics)
+    }
+
+    pub fn directives(&self) -> impl Iterator<Item = &Directive> {
+        self.entries.iter().filter_map(|entry| match entry {
+            Entry::Directive { directive, .. } => Some(directive),
+            _ => None,
+        })
+    }
+
+    /// Check the instructions against what Ollama accepts
+    pub fn validate(&self) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+        let mut seen_parameters: Vec<&str> = Vec::new();
+        let mut has_from = false;
+
+        for entry in &self.entries {
+            let (directive, line) = match entry {
+                Entry::Directive { directive, line, .. } => (directive, *line),
+                _ => continue,
+            };
+            match directive {
+                Directive::From { .. } => {
+                    if has_from {
+                        diagnostics.push(Diagnostic::warning(line, "Only the last FROM is used"));
+                    }
+                    has_from = true;
+                }
+                Directive::Parameter { name, value } => {
+                    if let Some(message) = check_parameter(name, value) {
+                        diagnostics.push(Diagnostic::error(line, message));
+                    } else if seen_parameters.contains(&name.as_str()) && !REPEATABLE_PARAMETERS.contains(&name.as_str()) {
+                        diagnostics.push(Diagnostic::warning(line, format!("PARAMETER {} is set more than once, the last value wins", name)));
+                    }
+                    seen_parameters.push(name);
+                }
+                _ => {}
+            }
+        }
+
+        let has_invalid = self.entries.iter().any(|e| matches!(e, Entry::Invalid { .. }));
+        if !has_from && !has_invalid {
+            diagnostics.push(Diagnostic::error(1, "A Modelfile needs a FROM instruction"));
+        }
+        diagnostics
+    }
+}
+
+// Serialize back; parsed entries are written exactly as they were read
+impl fmt::Display for Modelfile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for entry in &self.entries {
+            match entry {
+                Entry::Directive { raw: Some(raw), .. }
+                | Entry::Comment { raw: Some(raw), .. }
+                | Entry::Blank { raw, .. }
+                | Entry::Invalid { raw, .. } => write!(f, "{}", raw)?,
+                Entry::Directive { directive, raw: None, .. } => writeln!(f, "{}", directive)?,
+                Entry::Comment { text, raw: None, .. } => writeln!(f, "# {}", text)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+// Canonical form of a single instruction, without the trailing newline
+impl fmt::Display for Directive {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Directive::From { model } => write!(f, "FROM {}", quote(model)),
+            Directive::Parameter { name, value } => write!(f, "PARAMETER {} {}", name, quote(value)),
+            Directive::Template { template } => write!(f, "TEMPLATE {}", quote(template)),
+            Directive::System { system } => write!(f, "SYSTEM {}", quote(system)),
+            Directive::Adapter { path } => write!(f, "ADAPTER {}", quote(path)),
+            Directive::License { license } => write!(f, "LICENSE {}", quote(license)),
+            Directive::Message { role, content } => write!(f, "MESSAGE {} {}", role.as_str(), quote(content)),
+            Directive::Requires { version } => write!(f, "REQUIRES {}", quote(version)),
+        }
+    }
+}
+
+// Quote a value only when a bare value would not read back the same
+fn quote(value: &str) -> String {
+    if value.contains('\n') && !value.contains("\"\"\"") && !value.ends_with('"') {
+        format!("\"\"\"{}\"\"\"", value)
+    } else if value.is_empty() || value.trim() != value || value.starts_with('"') || value.contains('\n') {
+        format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn check_parameter(name: &str, value: &str) -> Option<String> {
+    let Some((_, parameter_type)) = KNOWN_PARAMETERS.iter().find(|(known, _)| *known == name) else {
+        return Some(format!("Unknown parameter {}", name));
+    };
+    let valid = match parameter_type {
+        ParameterType::Int => value.parse::<i64>().is_ok(),
+        ParameterType::Float => value.parse::<f64>().is_ok_and(|v| v.is_finite()),
+        ParameterType::Bool => value.parse::<bool>().is_ok(),
+        ParameterType::String => true,
+    };
+    if valid {
+        None
+    } else {
+        let expected = match parameter_type {
+            ParameterType::Int => "an integer",
+            ParameterType::Float => "a number",
+            ParameterType::Bool => "true or false",
+            ParameterType::String => "a string",
+        };
+        Some(format!("PARAMETER {} expects {}, got {:?}", name, expected, value))
+    }
+}
+
+// Split off the first whitespace-separated word
+fn split_word(text: &str) -> (&str, &str) {
+    let text = text.trim_start();
+    match text.find(char::is_whitespace) {
+        Some(index) => (&text[..index], &text[index..]),
+        None => (text, ""),
+    }
+}
+
+// Parse the instruction starting at `lines[start]`, returns it with the number of lines it spans.
+// On error the number of lines to skip is returned with the message.
+fn parse_instruction(lines: &[&str], start: usize) -> Result<(Directive, usize), (String, usize)> {
+    let (command, rest) = split_word(lines[start]);
+    let command = command.to_ascii_uppercase();
+
+    let (argument, rest) = match command.as_str() {
+        "PARAMETER" | "MESSAGE" => {
+            let (argument, rest) = split_word(rest);
+            if argument.is_empty() {
+                return Err((format!("{} needs a name and a value", command), 1));
+            }
+            (Some(argument.to_string()), rest)
+        }
+        "FROM" | "TEMPLATE" | "SYSTEM" | "ADAPTER" | "LICENSE" | "REQUIRES" => (None, rest),
+        _ => return Err((format!("Unknown instruction {:?}", command), 1)),
+    };
+
+    let (value, consumed) = parse_value(lines, start, rest)?;
+    if value.is_empty() && matches!(command.as_str(), "FROM" | "ADAPTER" | "REQUIRES" | "PARAMETER") {
+        return Err((format!("{} needs a value", command), consumed));
+    }
+
+    let directive = match command.as_str() {
+        "FROM" => Directive::From { model: value },
+        "PARAMETER" => Directive::Parameter { name: argument.unwrap_or_default().to_ascii_lowercase(), value },
+        "TEMPLATE" => Directive::Template { template: value },
+        "SYSTEM" => Directive::System { system: value },
+        "ADAPTER" => Directive::Adapter { path: value },
+        "LICENSE" => Directive::License { license: value },
+        "MESSAGE" => {
+            let role = argument.unwrap_or_default();
+            let role = MessageRole::parse(&role)
+                .ok_or_else(|| (format!("Unknown MESSAGE role {:?}, expected system, user or assistant", role), consumed))?;
+            Directive::Message { role, content: value }
+        }
+        _ => Directive::Requires { version: value },
+    };
+    Ok((directive, consumed))
+}
+
+// Parse a bare, "quoted" or """triple-quoted""" value that starts in `rest` (the remainder of
+// `lines[start]`); quoted values may continue on the following lines
+fn parse_value(lines: &[&str], start: usize, rest: &str) -> Result<(String, usize), (String, usize)> {
+    let rest = rest.trim_start();
+    let delimiter = if rest.starts_with("\"\"\"") {
+        "\"\"\""
+    } else if rest.starts_with('"') {
+        "\""
+    } else {
+        return Ok((rest.trim_end().to_string(), 1));
+    };
+
+    // Everything from the opening delimiter to the end of the input
+    let mut text = rest[delimiter.len()..].to_string();
+    for line in &lines[start + 1..] {
+        text.push_str(line);
+    }
+
+    let close = if delimiter == "\"" { find_closing_quote(&text) } else { text.find(delimiter) };
+    let Some(close) = close else {
+        return Err((format!("Missing closing {}", delimiter), lines.len() - start));
+    };
+
+    let consumed = text[..close].matches('\n').count() + 1;
+    let after = text[close + delimiter.len()..].split('\n').next().unwrap_or("");
+    if !after.trim().is_empty() {
+        return Err((format!("Unexpected text after closing {}: {:?}", delimiter, after.trim()), consumed));
+    }
+
+    let value = &text[..close];
+    let value = if delimiter == "\"" { unescape(value) } else { value.to_string() };
+    Ok((value, consumed))
+}
+
+// Index of the first `"` that is not escaped with a backslash
+fn find_closing_quote(text: &str) -> Option<usize> {
+    let mut escaped = false;
+    for (index, c) in text.char_indices() {
+        match c {
+            '\\' if !escaped => escaped = true,
+            '"' if !escaped => return Some(index),
+            _ => escaped = false,
+        }
+    }
+    None
+}
+
+fn unescape(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            match chars.next() {
+                Some(next @ ('"' | '\\')) => result.push(next),
+                Some(next) => {
+                    result.push('\\');
+                    result.push(next);
+                }
+                None => result.push('\\'),
+            }
+        } else {
+            result.push(c);
+        }
+    }
+    result
+}
+
+/// Tauri command: Parse a Modelfile into instructions and diagnostics for the editor
+#[tauri::command]
+pub fn parse_modelfile(content: String) -> ModelfileAnalysis {
+    let (modelfile, diagnostics) = Modelfile::parse(&content);
+    ModelfileAnalysis { modelfile, diagnostics }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "# Support bot\r\nFROM llama3.2:3b\nPARAMETER temperature 0.7\nparameter stop \"<|eot_id|>\"\nPARAMETER stop \"User:\"\n\nTEMPLATE \"\"\"{{ if .System }}<|system|>\n{{ .System }}{{ end }}\n\"\"\"\nSYSTEM \"You answer \\\"briefly\\\".\"\nADAPTER ./lora.gguf\nMESSAGE user Is Rust fast?\nMESSAGE assistant \"\"\"Yes.\"\"\"\nLICENSE \"\"\"MIT\n\nPermission is granted\"\"\"\nREQUIRES 0.5.0\n";
+
+    #[test]
+    fn test_parse_all_directives() {
+        let (modelfile, diagnostics) = Modelfile::parse(SAMPLE);
+        assert!(diagnostics.is_empty(), "{:?}", diagnostics);
+
+        let directives: Vec<&Directive> = modelfile.directives().collect();
+        assert_eq!(directives.len(), 11);
+        assert_eq!(directives[0], &Directive::From { model: "llama3.2:3b".to_string() });
+        assert_eq!(directives[2], &Directive::Parameter { name: "stop".to_string(), value: "<|eot_id|>".to_string() });
+        assert_eq!(directives[4], &Directive::Template { template: "{{ if .System }}<|system|>\n{{ .System }}{{ end }}\n".to_string() });
+        assert_eq!(directives[5], &Directive::System { system: "You answer \"briefly\".".to_string() });
+        assert_eq!(directives[7], &Directive::Message { role: MessageRole::User, content: "Is Rust fast?".to_string() });
+        assert_eq!(directives[9], &Directive::License { license: "MIT\n\nPermission is granted".to_string() });
+        assert_eq!(directives[10], &Directive::Requires { version: "0.5.0".to_string() });
+
+        // The LICENSE starts on line 14 and spans three lines
+        let lines: Vec<usize> = modelfile.entries.iter().map(|e| match e {
+            Entry::Directive { line, .. } | Entry::Comment { line, .. } | Entry::Blank { line, .. } | Entry::Invalid { line, .. } => *line,
+        }).collect();
+        assert_eq!(&lines[lines.len() - 2..], &[14, 17]);
+    }
+
+    #[test]
+    fn test_serialization_is_lossless() {
+        let (modelfile, _) = Modelfile::parse(SAMPLE);
+        assert_eq!(modelfile.to_string(), SAMPLE);
+
+        // Invalid lines survive a round trip too
+        let broken = "FROM base\nPARAMETR temperature 1\nSYSTEM \"\"\"never closed\n";
+        assert_eq!(Modelfile::parse(broken).0.to_string(), broken);
+
+        // Entries built in code are written in canonical form and parse back the same
+        let modelfile = Modelfile {
+            entries: modelfile.directives().cloned()
+                .map(|directive| Entry::Directive { directive, line: 0, raw: None })
+                .collect(),
+        };
+        let (reparsed, diagnostics) = Modelfile::parse(&modelfile.to_string());
+        assert!(diagnostics.is_empty(), "{:?}", diagnostics);
+        assert_eq!(reparsed.directives().collect::<Vec<_>>(), modelfile.directives().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_diagnostics_point_at_lines() {
+        let input = "PARAMETER num_ctx 4k\nPARAMETER temprature 0.5\nPARAMETER use_mmap yes\nFOO bar\nMESSAGE robot hi\nPARAMETER top_k 40\nPARAMETER top_k 20\nSYSTEM \"open\" trailing\n";
+        let (_, diagnostics) = Modelfile::parse(input);
+        let found: Vec<(usize, Severity)> = diagnostics.iter().map(|d| (d.line, d.severity)).collect();
+        assert_eq!(found, vec![
+            (1, Severity::Error),
+            (2, Severity::Error),
+            (3, Severity::Error),
+            (4, Severity::Error),
+            (5, Severity::Error),
+            (7, Severity::Warning),
+            (8, Severity::Error),
+        ]);
+        assert!(diagnostics[0].message.contains("num_ctx expects an integer"));
+
+        let (_, diagnostics) = Modelfile::parse("SYSTEM hello\n");
+        assert_eq!(diagnostics, vec![Diagnostic::error(1, "A Modelfile needs a FROM instruction")]);
+    }
+}
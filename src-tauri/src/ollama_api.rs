@@ -1,13 +1,20 @@
-use reqwest::{Client, StatusCode};
+use reqwest::StatusCode;
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
-use std::time::Duration;
-use std::sync::Mutex;
+use std::sync::{Mutex, OnceLock};
 use std::collections::HashMap;
 use tokio::sync::oneshot;
 use futures_util::StreamExt;
-use crate::config_manager::get_ollama_host;
+use crate::ollama_client::OllamaClient;
+use crate::ollama_error::OllamaError;
+use crate::json_schema::{self, SchemaError};
+use crate::download_queue::{DownloadQueue, DownloadStatus, QueueEntry};
+use crate::pull_progress::{PullProgressSummary, PullProgressTracker};
+use crate::pull_retry::PullRetryPolicy;
+use crate::download_schedule::DownloadWindow;
+use crate::model_store::{GcReport, ModelStore, ModelVerification, StoreInventory};
+use crate::tools::{ToolCall, ToolRegistry};
 use tauri::{command, Manager};
 use lazy_static::lazy_static;
 use std::fs;
@@ -18,6 +25,63 @@ use std::path::PathBuf;
 pub struct StreamResponse {
     pub content: String,
     pub done: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub role: Option<String>,
+    // Reasoning output of thinking models, streamed separately from content
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thinking: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_calls: Option<Vec<Value>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub done_reason: Option<String>,
+    // Timings and token counts, only set on the final event
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    pub metrics: Option<GenerationMetrics>,
+}
+
+// Streaming generate event, final stats are only set on the done event
+#[derive(Debug, Serialize, Clone)]
+pub struct GenerateStreamResponse {
+    pub content: String,
+    pub done: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub done_reason: Option<String>,
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    pub metrics: Option<GenerationMetrics>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub context: Option<Vec<i64>>,
+}
+
+// Tool-calling chat event, one per step of the model -> tool -> model loop
+#[derive(Debug, Serialize, Clone)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ToolChatEvent {
+    // Assistant message of a step, including any tool calls it requested
+    Assistant { step: usize, message: ChatMessage },
+    // Result of running one requested tool
+    ToolResult { step: usize, name: String, arguments: Value, content: String, is_error: bool },
+    // Final answer, or the cancellation of the loop
+    Done {
+        step: usize,
+        content: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        done_reason: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        metrics: Option<GenerationMetrics>,
+    },
+}
+
+// Result of generate_structured, either the validated JSON or the validation report
+#[derive(Debug, Serialize, Clone)]
+pub struct StructuredResponse {
+    pub valid: bool,
+    // Parsed output, present whenever the reply was valid JSON
+    pub value: Option<Value>,
+    pub raw: String,
+    pub attempts: u32,
+    // Validation errors of the last attempt, empty when valid
+    pub errors: Vec<SchemaError>,
+    pub metrics: GenerationMetrics,
 }
 
 // Pull model streaming response type
@@ -27,15 +91,46 @@ pub struct PullModelResponse {
     pub digest: Option<String>,
     pub total: Option<i64>,
     pub completed: Option<i64>,
+    // Progress over all layers, set once the first layer reported its size
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    pub progress: Option<PullProgressSummary>,
+    // Only set on "retrying" events
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    pub retry: Option<PullRetryInfo>,
+    // Only set on "waiting_for_window" events
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    pub window: Option<DownloadWindowWait>,
+    // Only set on "verified" and "verification_failed" events
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub verification: Option<ModelVerification>,
+}
+
+// Sent while a queued pull waits for the configured download window
+#[derive(Debug, Serialize, Clone)]
+pub struct DownloadWindowWait {
+    pub window_start: String,
+    pub window_end: String,
+    pub opens_in_seconds: u64,
+}
+
+// Details of an upcoming pull retry
+#[derive(Debug, Serialize, Clone)]
+pub struct PullRetryInfo {
+    // Number of the attempt that is about to start, the first pull is attempt 1
+    pub attempt: u32,
+    pub max_attempts: u32,
+    pub retry_in_ms: u64,
+    pub error: String,
 }
 
 // Structure to store download state for pause/resume
 struct DownloadState {
     cancel_tx: oneshot::Sender<()>,
     completed_bytes: i64,
+    total_bytes: i64,
 }
 
-// Persistent download progress structure
+// Persistent download progress structure, also holds the download queue across restarts
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct DownloadProgress {
     model_name: String,
@@ -43,66 +138,131 @@ pub struct DownloadProgress {
     completed_bytes: i64,
     total_bytes: i64,
     last_updated: u64, // timestamp
+    // Queue fields, records written before the queue existed load as paused
+    #[serde(default)]
+    status: DownloadStatus,
+    #[serde(default)]
+    priority: i32,
+    #[serde(default)]
+    position: usize,
+    #[serde(default)]
+    enqueued_at: u64,
+    // Server profile the pull runs against, `None` for the active server
+    #[serde(default)]
+    profile: Option<String>,
 }
 
+// Delivers the outcome of a queued download to a waiting pull_model call
+type DownloadResultSender = oneshot::Sender<Result<String, OllamaError>>;
+
 lazy_static! {
     // Global map to store active downloads with their state
     static ref ACTIVE_DOWNLOADS: Mutex<HashMap<String, DownloadState>> = Mutex::new(HashMap::new());
-    // Global map to store persistent download progress
-    static ref DOWNLOAD_PROGRESS: Mutex<HashMap<String, DownloadProgress>> = Mutex::new(HashMap::new());
+    // Pulls waiting for or holding a download slot
+    static ref DOWNLOAD_QUEUE: Mutex<DownloadQueue> = Mutex::new(DownloadQueue::new());
+    // pull_model calls waiting for the result of a queued download, keyed by channel id
+    static ref DOWNLOAD_WAITERS: Mutex<HashMap<String, Vec<DownloadResultSender>>> = Mutex::new(HashMap::new());
+    // Global map of in-flight chat/generate streams, keyed by request id
+    static ref ACTIVE_GENERATIONS: Mutex<HashMap<String, oneshot::Sender<()>>> = Mutex::new(HashMap::new());
+}
+
+// Event carrying the download queue snapshot whenever it changes
+const DOWNLOAD_QUEUE_EVENT: &str = "download-queue-changed";
+// Partial blobs written to more recently may belong to a paused download
+const GC_PARTIAL_MIN_AGE: std::time::Duration = std::time::Duration::from_secs(24 * 60 * 60);
+// Longest silence allowed between two status lines of a create or push,
+// quantizing a large model can take a while before the next line
+const STATUS_IDLE_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(30 * 60);
+// Longest silence allowed in a pull stream, Ollama can be quiet while it checks the digest of a large blob
+const PULL_IDLE_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10 * 60);
+// How often the scheduler checks the download window edges
+const DOWNLOAD_WINDOW_CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(30);
+// done_reason reported on the final stream event when the user stops a generation
+const DONE_REASON_CANCELLED: &str = "cancelled";
+// done_reason reported on the final stream event when the stream fails or ends without a done line
+const DONE_REASON_ERROR: &str = "error";
+// Upper bound of model -> tool -> model round trips in chat_with_tools
+const DEFAULT_MAX_TOOL_STEPS: usize = 8;
+// Upper bound for generate_structured retries, each one is a full generation
+const MAX_STRUCTURED_RETRIES: u32 = 5;
+
+// Removes an inference request from ACTIVE_GENERATIONS when its stream ends, whatever the exit path
+pub(crate) struct GenerationGuard {
+    request_id: String,
 }
 
+impl Drop for GenerationGuard {
+    fn drop(&mut self) {
+        ACTIVE_GENERATIONS.lock().unwrap().remove(&self.request_id);
+    }
+}
+
+// Register an in-flight inference request so cancel_generation can stop it
+pub(crate) fn register_generation(request_id: &str) -> Result<(oneshot::Receiver<()>, GenerationGuard), OllamaError> {
+    let mut active_generations = ACTIVE_GENERATIONS.lock().unwrap();
+    if active_generations.contains_key(request_id) {
+        return Err(OllamaError::Other(format!("Request id already in use: {}", request_id)));
+    }
+    
+    let (cancel_tx, cancel_rx) = oneshot::channel::<()>();
+    active_generations.insert(request_id.to_string(), cancel_tx);
+    
+    Ok((cancel_rx, GenerationGuard { request_id: request_id.to_string() }))
+}
+
+// Directory of the download progress file, tests point it to a temp dir before first use
+static PROGRESS_DIR: OnceLock<PathBuf> = OnceLock::new();
+
 // Helper functions for persistent storage
 fn get_progress_file_path() -> PathBuf {
-    let app_data_dir = dirs::config_dir()
-        .unwrap_or_else(|| PathBuf::from("."))
-        .join("ollama-pro");
+    let app_data_dir = PROGRESS_DIR.get_or_init(|| {
+        dirs::config_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("ollama-pro")
+    });
     
     // Ensure directory exists
     if !app_data_dir.exists() {
-        let _ = fs::create_dir_all(&app_data_dir);
+        let _ = fs::create_dir_all(app_data_dir);
     }
     
     app_data_dir.join("download_progress.json")
 }
 
-fn save_download_progress(channel_id: &str, progress: &DownloadProgress) {
+fn load_all_download_progress() -> HashMap<String, DownloadProgress> {
     let progress_file = get_progress_file_path();
     
+    if !progress_file.exists() {
+        return HashMap::new();
+    }
+    
+    fs::read_to_string(&progress_file)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn write_all_download_progress(all_progress: &HashMap<String, DownloadProgress>) {
+    if let Ok(json_content) = serde_json::to_string_pretty(all_progress) {
+        let _ = fs::write(get_progress_file_path(), json_content);
+    }
+}
+
+fn save_download_progress(channel_id: &str, progress: &DownloadProgress) {
     // Load existing progress data
-    let mut all_progress: HashMap<String, DownloadProgress> = if progress_file.exists() {
-        fs::read_to_string(&progress_file)
-            .ok()
-            .and_then(|content| serde_json::from_str(&content).ok())
-            .unwrap_or_default()
-    } else {
-        HashMap::new()
-    };
+    let mut all_progress = load_all_download_progress();
     
     // Update progress for this channel
     all_progress.insert(channel_id.to_string(), progress.clone());
     
     // Save back to file
-    if let Ok(json_content) = serde_json::to_string_pretty(&all_progress) {
-        let _ = fs::write(&progress_file, json_content);
-        println!("[PROGRESS] Save download progress to file: channel_id={}, completed_bytes={}", 
-                 channel_id, progress.completed_bytes);
-    }
+    write_all_download_progress(&all_progress);
+    println!("[PROGRESS] Save download progress to file: channel_id={}, completed_bytes={}", 
+             channel_id, progress.completed_bytes);
 }
 
 fn load_download_progress(channel_id: &str) -> Option<DownloadProgress> {
-    let progress_file = get_progress_file_path();
-    
-    if !progress_file.exists() {
-        return None;
-    }
-    
-    let all_progress: HashMap<String, DownloadProgress> = fs::read_to_string(&progress_file)
-        .ok()
-        .and_then(|content| serde_json::from_str(&content).ok())
-        .unwrap_or_default();
-    
-    let progress = all_progress.get(channel_id).cloned();
+    let progress = load_all_download_progress().remove(channel_id);
     if let Some(ref p) = progress {
         println!("[PROGRESS] Load download progress from file: channel_id={}, completed_bytes={}", 
                  channel_id, p.completed_bytes);
@@ -112,23 +272,383 @@ fn load_download_progress(channel_id: &str) -> Option<DownloadProgress> {
 }
 
 fn clear_download_progress(channel_id: &str) {
-    let progress_file = get_progress_file_path();
+    let mut all_progress = load_all_download_progress();
     
-    if !progress_file.exists() {
+    if all_progress.remove(channel_id).is_some() {
+        write_all_download_progress(&all_progress);
+        println!("[PROGRESS] Clear download progress file: channel_id={}", channel_id);
+    }
+}
+
+fn unix_timestamp() -> u64 {
+    std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap_or_default().as_secs()
+}
+
+// ---- Download queue ----
+
+// Write the queued and active entries to the progress file so the queue survives a restart
+fn persist_download_queue() {
+    let entries = DOWNLOAD_QUEUE.lock().unwrap().snapshot();
+    let mut all_progress = load_all_download_progress();
+    
+    for (position, entry) in entries.iter().enumerate() {
+        let live_bytes = ACTIVE_DOWNLOADS.lock().unwrap()
+            .get(&entry.channel_id)
+            .map(|state| (state.completed_bytes, state.total_bytes));
+        
+        let progress = all_progress.entry(entry.channel_id.clone()).or_insert_with(|| DownloadProgress {
+            model_name: entry.model_name.clone(),
+            channel_id: entry.channel_id.clone(),
+            completed_bytes: 0,
+            total_bytes: 0,
+            last_updated: 0,
+            status: entry.status,
+            priority: entry.priority,
+            position,
+            enqueued_at: entry.enqueued_at,
+            profile: entry.profile.clone(),
+        });
+        if let Some((completed_bytes, total_bytes)) = live_bytes {
+            progress.completed_bytes = completed_bytes;
+            progress.total_bytes = total_bytes;
+        }
+        progress.status = entry.status;
+        progress.priority = entry.priority;
+        progress.position = position;
+        progress.enqueued_at = entry.enqueued_at;
+        progress.last_updated = unix_timestamp();
+    }
+    
+    write_all_download_progress(&all_progress);
+}
+
+fn emit_download_queue(app_handle: &tauri::AppHandle) {
+    let entries = DOWNLOAD_QUEUE.lock().unwrap().snapshot();
+    let _ = app_handle.emit_all(DOWNLOAD_QUEUE_EVENT, entries);
+}
+
+// Persist and broadcast the queue after a change
+fn download_queue_changed(app_handle: &tauri::AppHandle) {
+    persist_download_queue();
+    emit_download_queue(app_handle);
+}
+
+fn enqueue_download(model_name: String, channel_id: String, priority: i32, profile: Option<String>, app_handle: &tauri::AppHandle) {
+    {
+        let mut queue = DOWNLOAD_QUEUE.lock().unwrap();
+        // A second pull for the same channel just waits for the queued one
+        if queue.contains(&channel_id) {
+            return;
+        }
+        println!("[QUEUE] Enqueue download: model={}, channel_id={}, priority={}", model_name, channel_id, priority);
+        queue.enqueue(QueueEntry {
+            channel_id,
+            model_name,
+            priority,
+            profile,
+            status: DownloadStatus::Queued,
+            enqueued_at: unix_timestamp(),
+            run: 0,
+        });
+    }
+    
+    download_queue_changed(app_handle);
+    start_queued_downloads(app_handle);
+}
+
+/// Start queued downloads while fewer than the configured maximum are active
+pub fn start_queued_downloads(app_handle: &tauri::AppHandle) {
+    // Outside the download window queued pulls only learn when they can start
+    if let Some((window, opens_in_seconds)) = closed_download_window() {
+        notify_waiting_for_window(&window, opens_in_seconds, app_handle);
         return;
     }
     
-    let mut all_progress: HashMap<String, DownloadProgress> = fs::read_to_string(&progress_file)
-        .ok()
-        .and_then(|content| serde_json::from_str(&content).ok())
+    let max_active = crate::config_manager::get_max_concurrent_downloads().unwrap_or_else(|e| {
+        println!("[DOWNLOAD] Could not read the concurrent download limit, using the default: {}", e);
+        crate::config_manager::DEFAULT_MAX_CONCURRENT_DOWNLOADS
+    });
+    let started = DOWNLOAD_QUEUE.lock().unwrap().start_next(max_active);
+    if started.is_empty() {
+        return;
+    }
+    
+    download_queue_changed(app_handle);
+    
+    for entry in started {
+        println!("[QUEUE] Start download: model={}, channel_id={}", entry.model_name, entry.channel_id);
+        let app_handle = app_handle.clone();
+        tauri::async_runtime::spawn(async move {
+            let result = match run_pull_with_retry(entry.model_name.clone(), entry.channel_id.clone(), entry.profile.clone(), app_handle.clone()).await {
+                // Only a pull Ollama reported as successful has a complete manifest to check
+                Ok(PullOutcome::Completed) => verify_pulled_model(&entry.model_name, &entry.channel_id, entry.profile.as_deref(), &app_handle).await
+                    .map(|_| PullOutcome::Completed.message().to_string()),
+                Ok(outcome) => Ok(outcome.message().to_string()),
+                Err(e) => Err(e),
+            };
+            finish_queued_download(&entry.channel_id, entry.run, result, &app_handle);
+        });
+    }
+}
+
+fn finish_queued_download(channel_id: &str, run: u64, result: Result<String, OllamaError>, app_handle: &tauri::AppHandle) {
+    let finished = {
+        let mut queue = DOWNLOAD_QUEUE.lock().unwrap();
+        match queue.get(channel_id) {
+            Some(entry) if entry.status == DownloadStatus::Active && entry.run == run => {
+                queue.remove(channel_id);
+                true
+            }
+            // Sent back to the queue by the download window (or already restarted), pull_model keeps waiting
+            Some(_) => return,
+            // Paused or cancelled downloads already left the queue and saved their progress
+            None => false,
+        }
+    };
+    
+    if finished {
+        let live_bytes = ACTIVE_DOWNLOADS.lock().unwrap()
+            .remove(channel_id)
+            .map(|state| (state.completed_bytes, state.total_bytes));
+        let status = if result.is_ok() { DownloadStatus::Completed } else { DownloadStatus::Failed };
+        if let Some(mut progress) = load_download_progress(channel_id) {
+            if let Some((completed_bytes, total_bytes)) = live_bytes {
+                progress.completed_bytes = completed_bytes;
+                progress.total_bytes = total_bytes;
+            }
+            if status == DownloadStatus::Completed {
+                progress.completed_bytes = progress.total_bytes;
+            }
+            progress.status = status;
+            progress.last_updated = unix_timestamp();
+            save_download_progress(channel_id, &progress);
+        }
+    }
+    emit_download_queue(app_handle);
+    notify_download_waiters(channel_id, result);
+    
+    // The slot is free again
+    start_queued_downloads(app_handle);
+}
+
+// Check the pulled blobs against the manifest before the pull counts as completed
+async fn verify_pulled_model(model_name: &str, channel_id: &str, profile: Option<&str>, app_handle: &tauri::AppHandle) -> Result<(), OllamaError> {
+    let event = |status: &str, verification: Option<ModelVerification>| PullModelResponse {
+        status: status.to_string(),
+        digest: None,
+        total: None,
+        completed: None,
+        progress: None,
+        retry: None,
+        window: None,
+        verification,
+    };
+    // The models directory only describes a local server, a remote host has nothing to check here
+    let server = crate::config_manager::resolve_server(profile).map_err(OllamaError::Other)?;
+    if !server.is_local() {
+        println!("[VERIFY] Skipped verification of {}: {} is a remote host", model_name, server.host);
+        return Ok(());
+    }
+    
+    let _ = app_handle.emit_all(channel_id, event("verifying", None));
+    let verification = run_model_verification(model_name, profile).await?;
+    
+    if verification.valid {
+        println!("[VERIFY] Model {} verified, {} blobs", model_name, verification.blobs.len());
+        let _ = app_handle.emit_all(channel_id, event("verified", Some(verification)));
+        return Ok(());
+    }
+    
+    let problems: Vec<String> = verification.problems()
+        .map(|b| format!("{} ({:?})", b.digest, b.status))
+        .collect();
+    println!("[VERIFY] Model {} failed verification: {}", model_name, problems.join(", "));
+    let _ = app_handle.emit_all(channel_id, event("verification_failed", Some(verification)));
+    Err(OllamaError::Other(format!("Model {} failed verification: {}", model_name, problems.join(", "))))
+}
+
+async fn run_model_verification(model_name: &str, profile: Option<&str>) -> Result<ModelVerification, OllamaError> {
+    let store = ModelStore::from_profile(profile).map_err(OllamaError::Other)?;
+    let model = model_name.to_string();
+    // Hashing multi-gigabyte blobs blocks, keep it off the async runtime
+    tokio::task::spawn_blocking(move || store.verify(&model))
+        .await
+        .map_err(|e| OllamaError::Other(format!("Verification task failed: {}", e)))?
+        .map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => OllamaError::ModelNotFound(format!("No manifest for model {}", model_name)),
+            _ => OllamaError::Other(format!("Failed to verify model {}: {}", model_name, e)),
+        })
+}
+
+// The configured download window and the seconds until it opens, `None` while downloads may run
+fn closed_download_window() -> Option<(DownloadWindow, u64)> {
+    let window = crate::config_manager::get_download_window().ok().flatten()?;
+    let opens_in_seconds = window.seconds_until_open_now()?;
+    Some((window, opens_in_seconds))
+}
+
+fn notify_waiting_for_window(window: &DownloadWindow, opens_in_seconds: u64, app_handle: &tauri::AppHandle) {
+    let waiting: Vec<QueueEntry> = DOWNLOAD_QUEUE.lock().unwrap()
+        .snapshot()
+        .into_iter()
+        .filter(|e| e.status == DownloadStatus::Queued)
+        .collect();
+    
+    for entry in waiting {
+        let _ = app_handle.emit_all(&entry.channel_id, PullModelResponse {
+            status: "waiting_for_window".to_string(),
+            digest: None,
+            total: None,
+            completed: None,
+            progress: None,
+            retry: None,
+            window: Some(DownloadWindowWait {
+                window_start: window.start.clone(),
+                window_end: window.end.clone(),
+                opens_in_seconds,
+            }),
+            verification: None,
+        });
+    }
+}
+
+/// Whether a pull is running, its blobs are written to the models directory
+pub fn has_active_downloads() -> bool {
+    !ACTIVE_DOWNLOADS.lock().unwrap().is_empty()
+}
+
+/// Pause running downloads outside the download window and start queued ones inside it
+pub fn apply_download_window(app_handle: &tauri::AppHandle) {
+    if closed_download_window().is_some() {
+        defer_active_downloads(app_handle);
+    }
+    start_queued_downloads(app_handle);
+}
+
+// Stop the running pulls through their cancel channel but keep them queued,
+// they are pulled again (and Ollama resumes them) once the window opens
+fn defer_active_downloads(app_handle: &tauri::AppHandle) {
+    let active: Vec<String> = DOWNLOAD_QUEUE.lock().unwrap()
+        .snapshot()
+        .into_iter()
+        .filter(|e| e.status == DownloadStatus::Active)
+        .map(|e| e.channel_id)
+        .collect();
+    if active.is_empty() {
+        return;
+    }
+    
+    // Save the live bytes before the download states go away
+    persist_download_queue();
+    
+    for channel_id in &active {
+        println!("[QUEUE] Download window closed, deferring download: channel_id={}", channel_id);
+        DOWNLOAD_QUEUE.lock().unwrap().defer(channel_id);
+        if let Some(download_state) = ACTIVE_DOWNLOADS.lock().unwrap().remove(channel_id) {
+            let _ = download_state.cancel_tx.send(());
+        }
+    }
+    
+    download_queue_changed(app_handle);
+}
+
+/// Re-check the download window periodically so downloads pause and resume at its edges
+pub fn start_download_scheduler(app_handle: tauri::AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(DOWNLOAD_WINDOW_CHECK_INTERVAL);
+        loop {
+            interval.tick().await;
+            apply_download_window(&app_handle);
+        }
+    });
+}
+
+fn notify_download_waiters(channel_id: &str, result: Result<String, OllamaError>) {
+    let waiters = DOWNLOAD_WAITERS.lock().unwrap().remove(channel_id).unwrap_or_default();
+    for waiter in waiters {
+        let _ = waiter.send(result.clone());
+    }
+}
+
+// Stop a queued or running download and save its progress as paused, returns whether anything was stopped
+fn stop_download(channel_id: &str, stopped_message: &str) -> bool {
+    // Leave the queue first so the finishing download doesn't overwrite the saved progress
+    let queue_entry = DOWNLOAD_QUEUE.lock().unwrap().remove(channel_id);
+    let state = ACTIVE_DOWNLOADS.lock().unwrap().remove(channel_id);
+    if queue_entry.is_none() && state.is_none() {
+        return false;
+    }
+    
+    // A download that was resumed but never got a slot keeps its previously saved bytes
+    let previous = load_download_progress(channel_id);
+    let (completed_bytes, total_bytes) = match (&state, &previous) {
+        (Some(state), _) => (state.completed_bytes, state.total_bytes),
+        (None, Some(previous)) => (previous.completed_bytes, previous.total_bytes),
+        (None, None) => (0, 0),
+    };
+    let model_name = queue_entry.as_ref().map(|e| e.model_name.clone())
+        .or_else(|| previous.as_ref().map(|p| p.model_name.clone()))
         .unwrap_or_default();
     
-    all_progress.remove(channel_id);
+    save_download_progress(channel_id, &DownloadProgress {
+        model_name,
+        channel_id: channel_id.to_string(),
+        completed_bytes,
+        total_bytes,
+        last_updated: unix_timestamp(),
+        status: DownloadStatus::Paused,
+        priority: queue_entry.as_ref().map(|e| e.priority).unwrap_or(0),
+        position: 0,
+        enqueued_at: queue_entry.as_ref().map(|e| e.enqueued_at).unwrap_or(0),
+        profile: queue_entry.as_ref().map(|e| e.profile.clone()).unwrap_or_else(|| previous.as_ref().and_then(|p| p.profile.clone())),
+    });
     
-    if let Ok(json_content) = serde_json::to_string_pretty(&all_progress) {
-        let _ = fs::write(&progress_file, json_content);
-        println!("[PROGRESS] Clear download progress file: channel_id={}", channel_id);
+    match state {
+        Some(download_state) => {
+            // The running pull answers its waiters once it sees the signal
+            if download_state.cancel_tx.send(()).is_err() {
+                println!("[DOWNLOAD] Download already finished when stopping: channel_id={}", channel_id);
+            }
+        }
+        None => {
+            // Never started, nothing to stop but the waiting pull_model calls
+            notify_download_waiters(channel_id, Ok(stopped_message.to_string()));
+        }
     }
+    
+    true
+}
+
+/// Re-queue the downloads that were queued or running when the app last exited
+pub fn restore_download_queue(app_handle: &tauri::AppHandle) {
+    let mut pending: Vec<DownloadProgress> = load_all_download_progress()
+        .into_values()
+        .filter(|p| matches!(p.status, DownloadStatus::Queued | DownloadStatus::Active))
+        .collect();
+    if pending.is_empty() {
+        return;
+    }
+    pending.sort_by_key(|p| p.position);
+    
+    {
+        let mut queue = DOWNLOAD_QUEUE.lock().unwrap();
+        for progress in pending {
+            println!("[QUEUE] Restore download: model={}, channel_id={}", progress.model_name, progress.channel_id);
+            // Keep the saved order, it may differ from plain priority order after a reorder
+            queue.push_back(QueueEntry {
+                channel_id: progress.channel_id,
+                model_name: progress.model_name,
+                priority: progress.priority,
+                profile: progress.profile,
+                status: DownloadStatus::Queued,
+                enqueued_at: progress.enqueued_at,
+                run: 0,
+            });
+        }
+    }
+    
+    download_queue_changed(app_handle);
+    start_queued_downloads(app_handle);
 }
 
 // ---- Model Types ----
@@ -195,9 +715,14 @@ pub struct ChatMessage {
     pub role: String,
     pub content: String,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub thinking: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub images: Option<Vec<String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tool_calls: Option<Vec<Value>>,
+    // Name of the tool a `tool` role message answers
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_name: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -245,6 +770,13 @@ pub struct GenerateResponse {
     pub model: String,
     pub response: String,
     pub done: bool,
+    // Only present on the final response
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub done_reason: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub context: Option<Vec<i64>>,
+    #[serde(flatten)]
+    pub metrics: GenerationMetrics,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -252,6 +784,49 @@ pub struct ChatResponse {
     pub model: String,
     pub message: ChatMessage,
     pub done: bool,
+    // Only present on the final response
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub done_reason: Option<String>,
+    #[serde(flatten)]
+    pub metrics: GenerationMetrics,
+}
+
+// Timings (in nanoseconds) and token counts Ollama reports on the final response
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct GenerationMetrics {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_duration: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub load_duration: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prompt_eval_count: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prompt_eval_duration: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub eval_count: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub eval_duration: Option<i64>,
+    // Derived by us, not sent by Ollama
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prompt_tokens_per_second: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tokens_per_second: Option<f64>,
+}
+
+impl GenerationMetrics {
+    /// Fill in the derived prompt and generation throughput
+    pub fn with_throughput(mut self) -> Self {
+        self.prompt_tokens_per_second = tokens_per_second(self.prompt_eval_count, self.prompt_eval_duration);
+        self.tokens_per_second = tokens_per_second(self.eval_count, self.eval_duration);
+        self
+    }
+}
+
+fn tokens_per_second(count: Option<i64>, duration_ns: Option<i64>) -> Option<f64> {
+    match (count, duration_ns) {
+        (Some(count), Some(duration)) if duration > 0 => Some(count as f64 / (duration as f64 / 1_000_000_000.0)),
+        _ => None,
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -271,203 +846,242 @@ pub struct EmbeddingsResponse {
 
 // ---- API Implementation ----
 
-fn get_client() -> Client {
-    Client::builder()
-        .timeout(Duration::from_secs(60))
-        .build()
-        .unwrap_or_default()
+// Take all complete lines out of an NDJSON byte buffer, leaving a trailing partial line in place.
+// Working on bytes avoids dropping chunks that split a multi-byte UTF-8 character.
+fn drain_complete_lines(buffer: &mut Vec<u8>) -> Vec<String> {
+    let mut lines = Vec::new();
+    while let Some(newline_pos) = buffer.iter().position(|b| *b == b'\n') {
+        let line: Vec<u8> = buffer.drain(..=newline_pos).collect();
+        let line = String::from_utf8_lossy(&line).trim().to_string();
+        if !line.is_empty() {
+            lines.push(line);
+        }
+    }
+    lines
+}
+
+// Build a client for a server profile, or for the active server when `None`
+fn get_client(profile: Option<&str>) -> Result<OllamaClient, OllamaError> {
+    OllamaClient::from_profile(profile)
 }
 
 #[command]
-pub async fn check_connection() -> Result<bool, String> {
-    let base_url = get_ollama_host().map_err(|e| e.to_string())?;
-    
-    let client = get_client();
-    let url = format!("{}/api/version", base_url);
-    
-    match client.get(&url).timeout(Duration::from_secs(5)).send().await {
-        Ok(response) => Ok(response.status().is_success()),
-        Err(_) => Ok(false),
-    }
+pub async fn check_connection(profile: Option<String>) -> Result<bool, OllamaError> {
+    let client = get_client(profile.as_deref())?;
+    client.is_reachable().await
 }
 
 #[command]
-pub async fn get_version() -> Result<OllamaVersion, String> {
-    let base_url = get_ollama_host().map_err(|e| e.to_string())?;
-    
-    let client = get_client();
-    let url = format!("{}/api/version", base_url);
-    
-    let response = client.get(&url).send().await
-        .map_err(|e| format!("Failed to get version: {}", e))?;
-        
-    if !response.status().is_success() {
-        return Err(format!("HTTP error: {}", response.status()));
-    }
-    
-    response.json::<OllamaVersion>().await
-        .map_err(|e| format!("Failed to parse version response: {}", e))
+pub async fn get_version(profile: Option<String>) -> Result<OllamaVersion, OllamaError> {
+    let client = get_client(profile.as_deref())?;
+    client.version().await
 }
 
 #[command]
-pub async fn list_models() -> Result<Vec<OllamaModel>, String> {
-    let base_url = get_ollama_host().map_err(|e| e.to_string())?;
-    
-    let client = get_client();
-    let url = format!("{}/api/tags", base_url);
-    
-    let response = client.get(&url).send().await
-        .map_err(|e| format!("Failed to list models: {}", e))?;
-        
-    if !response.status().is_success() {
-        return Err(format!("HTTP error: {}", response.status()));
-    }
-    
-    let model_list: OllamaModelList = response.json().await
-        .map_err(|e| format!("Failed to parse models response: {}", e))?;
-        
-    Ok(model_list.models)
+pub async fn list_models(profile: Option<String>) -> Result<Vec<OllamaModel>, OllamaError> {
+    let client = get_client(profile.as_deref())?;
+    client.list_models().await
 }
 
 #[command]
-pub async fn list_running_models() -> Result<Vec<OllamaRunningModel>, String> {
-    let base_url = get_ollama_host().map_err(|e| e.to_string())?;
-    
-    let client = get_client();
-    let url = format!("{}/api/ps", base_url);
-    
-    let response = client.get(&url).send().await
-        .map_err(|_| "Failed to fetch running models".to_string())?;
-        
-    if !response.status().is_success() {
-        return Err("Failed to fetch running models".to_string());
-    }
-    
-    let model_list: OllamaRunningModelList = response.json().await
-        .map_err(|_| "Failed to parse running models response".to_string())?;
-        
-    Ok(model_list.models)
+pub async fn list_running_models(profile: Option<String>) -> Result<Vec<OllamaRunningModel>, OllamaError> {
+    let client = get_client(profile.as_deref())?;
+    client.list_running_models().await
 }
 
 #[command]
-pub async fn show_model_info(model_name: String, verbose: bool) -> Result<ModelInfo, String> {
-    let base_url = get_ollama_host().map_err(|e| e.to_string())?;
-    
-    let client = get_client();
-    let url = format!("{}/api/show", base_url);
-    
-    let payload = json!({
-        "model": model_name,
-        "verbose": verbose
-    });
-    
-    let response = client.post(&url)
-        .json(&payload)
-        .send().await
-        .map_err(|e| format!("Failed to get model info: {}", e))?;
-        
-    if !response.status().is_success() {
-        return Err(format!("HTTP error: {}", response.status()));
-    }
-    
-    response.json::<ModelInfo>().await
-        .map_err(|e| format!("Failed to parse model info response: {}", e))
+pub async fn show_model_info(model_name: String, verbose: bool, profile: Option<String>) -> Result<ModelInfo, OllamaError> {
+    let client = get_client(profile.as_deref())?;
+    client.show_model(&model_name, verbose).await
 }
 
+/// Re-hash the blobs of a local model and report missing or corrupt ones
 #[command]
-pub async fn copy_model(source: String, destination: String) -> Result<bool, String> {
-    let base_url = get_ollama_host().map_err(|e| e.to_string())?;
-    
-    let client = get_client();
-    let url = format!("{}/api/copy", base_url);
-    
-    let payload = json!({
-        "source": source,
-        "destination": destination
-    });
-    
-    let response = client.post(&url)
-        .json(&payload)
-        .send().await
-        .map_err(|e| format!("Failed to copy model: {}", e))?;
-        
-    Ok(response.status().is_success())
+pub async fn verify_model(model_name: String, profile: Option<String>) -> Result<ModelVerification, OllamaError> {
+    run_model_verification(&model_name, profile.as_deref()).await
+}
+
+/// Manifests and blobs in the local models directory, with shared and orphaned blobs
+#[command]
+pub async fn inspect_model_store(profile: Option<String>) -> Result<StoreInventory, OllamaError> {
+    let store = ModelStore::from_profile(profile.as_deref()).map_err(OllamaError::Other)?;
+    tokio::task::spawn_blocking(move || store.inventory())
+        .await
+        .map_err(|e| OllamaError::Other(format!("Inspection task failed: {}", e)))?
+        .map_err(|e| OllamaError::Other(format!("Failed to inspect models directory: {}", e)))
 }
 
+/// Find orphaned blobs and stale partial downloads in the models directory
+///
+/// With `dry_run` only the candidates and the reclaimable bytes are reported.
+/// Otherwise the candidates listed in `confirmed` (paths from a dry run) are deleted.
 #[command]
-pub async fn delete_model(model_name: String) -> Result<bool, String> {
-    let base_url = get_ollama_host().map_err(|e| e.to_string())?;
+pub async fn gc_models_dir(dry_run: bool, confirmed: Option<Vec<String>>, profile: Option<String>) -> Result<GcReport, OllamaError> {
+    // Blobs of a running pull are not referenced until its manifest is written
+    let active = ACTIVE_DOWNLOADS.lock().unwrap().len();
+    if active > 0 {
+        return Err(OllamaError::Other(format!("Cannot clean up the models directory while {} download(s) are running", active)));
+    }
+    let confirmed = match confirmed {
+        Some(confirmed) => confirmed,
+        None if dry_run => Vec::new(),
+        None => return Err(OllamaError::Other("Deleting requires the confirmed paths from a dry run".to_string())),
+    };
     
-    let client = get_client();
-    let url = format!("{}/api/delete", base_url);
+    let store = ModelStore::from_profile(profile.as_deref()).map_err(OllamaError::Other)?;
+    let report = tokio::task::spawn_blocking(move || store.gc(dry_run, &confirmed, GC_PARTIAL_MIN_AGE))
+        .await
+        .map_err(|e| OllamaError::Other(format!("Cleanup task failed: {}", e)))?
+        .map_err(|e| OllamaError::Other(format!("Failed to clean up models directory: {}", e)))?;
     
-    let payload = json!({
-        "model": model_name
-    });
-    
-    let response = client.delete(&url)
-        .json(&payload)
-        .send().await
-        .map_err(|e| format!("Failed to delete model: {}", e))?;
-        
-    Ok(response.status().is_success() || response.status() == StatusCode::NOT_FOUND)
+    println!("[GC] dry_run={}, candidates={}, reclaimable={} bytes, freed={} bytes",
+        report.dry_run, report.candidates.len(), report.reclaimable_bytes, report.freed_bytes);
+    Ok(report)
+}
+
+#[command]
+pub async fn copy_model(source: String, destination: String, profile: Option<String>) -> Result<(), OllamaError> {
+    let client = get_client(profile.as_deref())?;
+    client.copy_model(&source, &destination).await
 }
 
 #[command]
-pub async fn generate_completion(request: GenerateRequest) -> Result<String, String> {
-    let base_url = get_ollama_host().map_err(|e| e.to_string())?;
+pub async fn delete_model(model_name: String, profile: Option<String>) -> Result<(), OllamaError> {
+    let client = get_client(profile.as_deref())?;
+    client.delete_model(&model_name).await
+}
+
+#[command]
+pub async fn generate_completion(request: GenerateRequest, profile: Option<String>) -> Result<String, OllamaError> {
+    let client = get_client(profile.as_deref())?;
     
     // Create a non-streaming request
     let mut req = request;
     req.stream = Some(false);
     
-    let client = get_client();
-    let url = format!("{}/api/generate", base_url);
-    
-    let response = client.post(&url)
-        .json(&req)
-        .send().await
-        .map_err(|e| format!("Failed to generate completion: {}", e))?;
-        
-    if !response.status().is_success() {
-        return Err(format!("HTTP error: {}", response.status()));
-    }
-    
-    let generate_response: GenerateResponse = response.json().await
-        .map_err(|e| format!("Failed to parse generate response: {}", e))?;
+    let generate_response = client.generate(&req).await?;
         
     Ok(generate_response.response)
 }
 
 #[command]
-pub async fn generate_chat(request: ChatRequest) -> Result<ChatMessage, String> {
-    let base_url = get_ollama_host().map_err(|e| e.to_string())?;
+pub async fn generate_completion_stream(request: GenerateRequest, channel_id: String, profile: Option<String>, app_handle: tauri::AppHandle) -> Result<String, OllamaError> {
+    // The channel id doubles as the request id for cancel_generation
+    let (mut cancel_rx, _guard) = register_generation(&channel_id)?;
+    let client = get_client(profile.as_deref())?;
     
-    // Create a non-streaming request
     let mut req = request;
-    req.stream = Some(false);
+    req.stream = Some(true);
+    
+    let response = client.generate_stream(&req).await?;
     
-    let client = get_client();
-    let url = format!("{}/api/chat", base_url);
+    // Events go to the caller-supplied channel so parallel completions don't mix
+    let event_name = channel_id;
+    let mut full_response = String::new();
+    let mut buffer: Vec<u8> = Vec::new();
+    let mut stream = response.bytes_stream();
     
-    let response = client.post(&url)
-        .json(&req)
-        .send().await
-        .map_err(|e| format!("Failed to generate chat response: {}", e))?;
+    let result = async {
+        loop {
+            let chunk_result = tokio::select! {
+                _ = &mut cancel_rx => {
+                    // Dropping the stream closes the HTTP connection, which stops generation in Ollama
+                    println!("[GENERATE] Generation cancelled: channel_id={}", event_name);
+                    let _ = app_handle.emit_all(&event_name, GenerateStreamResponse {
+                        content: String::new(),
+                        done: true,
+                        done_reason: Some(DONE_REASON_CANCELLED.to_string()),
+                        metrics: None,
+                        context: None,
+                    });
+                    return Err(OllamaError::Cancelled);
+                }
+                chunk = stream.next() => match chunk {
+                    Some(chunk_result) => chunk_result,
+                    None => break, // End of stream
+                },
+            };
+            
+            let bytes = chunk_result?;
+            buffer.extend_from_slice(&bytes);
+            
+            for line in drain_complete_lines(&mut buffer) {
+                let generate_response = match serde_json::from_str::<GenerateResponse>(&line) {
+                    Ok(generate_response) => generate_response,
+                    Err(e) => {
+                        // Ollama reports failures such as running out of memory after the 200 status as an error line
+                        if let Some(error) = serde_json::from_str::<Value>(&line).ok().and_then(|v| v["error"].as_str().map(str::to_string)) {
+                            return Err(OllamaError::Other(error));
+                        }
+                        // Parse error, log but continue processing
+                        eprintln!("Error parsing generate stream JSON: {}, raw: {}", e, line);
+                        continue;
+                    }
+                };
+                
+                // Only send incremental content, not accumulated
+                if !generate_response.response.is_empty() {
+                    let _ = app_handle.emit_all(&event_name, GenerateStreamResponse {
+                        content: generate_response.response.clone(),
+                        done: false,
+                        done_reason: None,
+                        metrics: None,
+                        context: None,
+                    });
+                    full_response.push_str(&generate_response.response);
+                }
+                
+                // Final chunk carries the stats and the context for follow-up requests
+                if generate_response.done {
+                    let _ = app_handle.emit_all(&event_name, GenerateStreamResponse {
+                        content: String::new(),
+                        done: true,
+                        done_reason: generate_response.done_reason,
+                        metrics: Some(generate_response.metrics.with_throughput()),
+                        context: generate_response.context,
+                    });
+                    return Ok(());
+                }
+            }
+        }
         
-    if !response.status().is_success() {
-        return Err(format!("HTTP error: {}", response.status()));
+        Err(OllamaError::Interrupted("Generation stream ended before Ollama reported done".to_string()))
+    }.await;
+    
+    match result {
+        Ok(()) => Ok(full_response),
+        Err(OllamaError::Cancelled) => Err(OllamaError::Cancelled),
+        Err(e) => {
+            // Listeners wait for a done event, so failures end the stream the same way a cancel does
+            println!("[GENERATE] Generation failed: channel_id={}, error={}", event_name, e);
+            let _ = app_handle.emit_all(&event_name, GenerateStreamResponse {
+                content: String::new(),
+                done: true,
+                done_reason: Some(DONE_REASON_ERROR.to_string()),
+                metrics: None,
+                context: None,
+            });
+            Err(e)
+        }
     }
+}
+
+#[command]
+pub async fn generate_chat(request: ChatRequest, profile: Option<String>) -> Result<ChatMessage, OllamaError> {
+    let client = get_client(profile.as_deref())?;
+    
+    // Create a non-streaming request
+    let mut req = request;
+    req.stream = Some(false);
     
-    let chat_response: ChatResponse = response.json().await
-        .map_err(|e| format!("Failed to parse chat response: {}", e))?;
+    let chat_response = client.chat(&req).await?;
         
     Ok(chat_response.message)
 }
 
 #[command]
-pub async fn generate_embeddings(model: String, input: Value, options: Option<Value>) -> Result<EmbeddingsResponse, String> {
-    let base_url = get_ollama_host().map_err(|e| e.to_string())?;
+pub async fn generate_embeddings(model: String, input: Value, options: Option<Value>, profile: Option<String>) -> Result<EmbeddingsResponse, OllamaError> {
+    let client = get_client(profile.as_deref())?;
     
     let request = EmbeddingsRequest {
         model,
@@ -475,29 +1089,114 @@ pub async fn generate_embeddings(model: String, input: Value, options: Option<Va
         options,
     };
     
-    let client = get_client();
-    let url = format!("{}/api/embed", base_url);
+    client.embed(&request).await
+}
+
+// Turns the bytes of a create or push stream into status events and remembers whether Ollama reported success
+struct StatusStreamReader {
+    buffer: Vec<u8>,
+    progress_tracker: PullProgressTracker,
+    succeeded: bool,
+}
+
+impl StatusStreamReader {
+    fn new() -> Self {
+        Self {
+            buffer: Vec::new(),
+            progress_tracker: PullProgressTracker::new(),
+            succeeded: false,
+        }
+    }
     
-    let response = client.post(&url)
-        .json(&request)
-        .send().await
-        .map_err(|e| format!("Failed to generate embeddings: {}", e))?;
+    // Status events for the complete lines in `chunk`, an error line fails the stream
+    fn feed(&mut self, chunk: &[u8]) -> Result<Vec<PullModelResponse>, OllamaError> {
+        self.buffer.extend_from_slice(chunk);
+        let mut events = Vec::new();
         
-    if !response.status().is_success() {
-        return Err(format!("HTTP error: {}", response.status()));
+        for line in drain_complete_lines(&mut self.buffer) {
+            let json_value = match serde_json::from_str::<Value>(&line) {
+                Ok(json_value) => json_value,
+                Err(e) => {
+                    println!("Failed to parse status line: {}, raw: {}", e, line);
+                    continue;
+                }
+            };
+            // Ollama reports failures after the 200 status as an error line
+            if let Some(error) = json_value["error"].as_str() {
+                return Err(OllamaError::Other(error.to_string()));
+            }
+            
+            let status = json_value["status"].as_str().unwrap_or("unknown").to_string();
+            if status == "success" {
+                self.succeeded = true;
+            }
+            let digest = json_value["digest"].as_str().map(|s| s.to_string());
+            let total = json_value["total"].as_i64();
+            let completed = json_value["completed"].as_i64();
+            if let Some(layer_digest) = &digest {
+                self.progress_tracker.update(layer_digest, total, completed, std::time::Instant::now());
+            }
+            
+            events.push(PullModelResponse {
+                status,
+                digest,
+                total,
+                completed,
+                progress: Some(self.progress_tracker.summary()).filter(|p| p.layer_count > 0),
+                retry: None,
+                window: None,
+                verification: None,
+            });
+        }
+        
+        Ok(events)
     }
     
-    response.json::<EmbeddingsResponse>().await
-        .map_err(|e| format!("Failed to parse embeddings response: {}", e))
+    // A stream that closes before the success line was cut off, e.g. by a restarting server
+    fn finish(self) -> Result<(), OllamaError> {
+        if self.succeeded {
+            Ok(())
+        } else {
+            Err(OllamaError::Interrupted("Stream ended before Ollama reported success".to_string()))
+        }
+    }
 }
 
+/// Forward the status lines of a create or push stream to `channel_id`
+///
+/// Lines with a digest carry per-layer bytes and get the same aggregated
+/// progress as pulls. Fails unless the stream ends with Ollama's success line.
+pub(crate) async fn forward_status_stream(response: reqwest::Response, channel_id: &str, app_handle: &tauri::AppHandle) -> Result<(), OllamaError> {
+    let mut stream = response.bytes_stream();
+    let mut reader = StatusStreamReader::new();
+    
+    loop {
+        let chunk = match tokio::time::timeout(STATUS_IDLE_TIMEOUT, stream.next()).await {
+            Ok(Some(chunk)) => chunk?,
+            Ok(None) => break,
+            Err(_) => {
+                return Err(OllamaError::Timeout(format!("No status from Ollama for {} seconds", STATUS_IDLE_TIMEOUT.as_secs())));
+            }
+        };
+        
+        for event in reader.feed(&chunk)? {
+            let _ = app_handle.emit_all(channel_id, event);
+        }
+    }
+    
+    reader.finish()
+}
+
+/// Create a model, streaming its status lines to `channel_id`
+///
+/// The channel id doubles as the request id for cancel_generation.
 #[command]
-pub async fn create_model(model_name: String, from: Option<String>, modelfile: Option<String>) -> Result<String, String> {
-    let base_url = get_ollama_host().map_err(|e| e.to_string())?;
+pub async fn create_model(model_name: String, from: Option<String>, modelfile: Option<String>, channel_id: String, profile: Option<String>, app_handle: tauri::AppHandle) -> Result<String, OllamaError> {
+    let (mut cancel_rx, _guard) = register_generation(&channel_id)?;
+    let client = get_client(profile.as_deref())?;
     
     let mut payload = json!({
-        "model": model_name,
-        "stream": false
+        "model": model_name
     });
     
     if let Some(from_val) = from {
@@ -508,23 +1207,24 @@ pub async fn create_model(model_name: String, from: Option<String>, modelfile: O
         payload["modelfile"] = json!(modelfile_val);
     }
     
-    let client = get_client();
-    let url = format!("{}/api/create", base_url);
-    
-    let response = client.post(&url)
-        .json(&payload)
-        .send().await
-        .map_err(|e| format!("Failed to create model: {}", e))?;
-        
-    if !response.status().is_success() {
-        return Err(format!("HTTP error: {}", response.status()));
+    tokio::select! {
+        _ = &mut cancel_rx => {
+            // Dropping the request closes the connection, which stops the create in Ollama
+            println!("[CREATE] Create cancelled: model={}, channel_id={}", model_name, channel_id);
+            Err(OllamaError::Cancelled)
+        }
+        result = async {
+            let response = client.create_stream(&payload).await?;
+            forward_status_stream(response, &channel_id, &app_handle).await
+        } => {
+            result?;
+            Ok("Model created successfully".to_string())
+        }
     }
-    
-    Ok("Model created successfully".to_string())
 }
 
 #[command]
-pub async fn load_model(model_name: String) -> Result<bool, String> {
+pub async fn load_model(model_name: String, profile: Option<String>) -> Result<bool, OllamaError> {
     let generate_request = GenerateRequest {
         model: model_name,
         prompt: "".to_string(),
@@ -539,14 +1239,14 @@ pub async fn load_model(model_name: String) -> Result<bool, String> {
         keep_alive: None,
     };
     
-    match generate_completion(generate_request).await {
+    match generate_completion(generate_request, profile.clone()).await {
         Ok(_) => Ok(true),
         Err(_) => Ok(false)
     }
 }
 
 #[command]
-pub async fn unload_model(model_name: String) -> Result<bool, String> {
+pub async fn unload_model(model_name: String, profile: Option<String>) -> Result<bool, OllamaError> {
     let generate_request = GenerateRequest {
         model: model_name,
         prompt: "".to_string(),
@@ -561,37 +1261,141 @@ pub async fn unload_model(model_name: String) -> Result<bool, String> {
         keep_alive: Some(json!(0)),
     };
     
-    match generate_completion(generate_request).await {
+    match generate_completion(generate_request, profile.clone()).await {
         Ok(_) => Ok(true),
         Err(_) => Ok(false)
     }
 }
 
 #[command]
-pub async fn pull_model(model_name: String, channel_id: String, app_handle: tauri::AppHandle) -> Result<String, String> {
+pub async fn pull_model(model_name: String, channel_id: String, priority: Option<i32>, profile: Option<String>, app_handle: tauri::AppHandle) -> Result<String, OllamaError> {
     // More detailed logging to track each incoming request
     println!("=====================================");
     println!("[PULL_MODEL] Received download request: model={}, channel_id={}, time={:?}", 
              model_name, channel_id, std::time::SystemTime::now());
     println!("=====================================");
     
-    let base_url = get_ollama_host().map_err(|e| e.to_string())?;
-    println!("[PULL_MODEL] Using Ollama server address: {}", base_url);
+    // The download runs from the queue, wait until it finishes
+    let (result_tx, result_rx) = oneshot::channel();
+    DOWNLOAD_WAITERS.lock().unwrap().entry(channel_id.clone()).or_default().push(result_tx);
+    enqueue_download(model_name, channel_id, priority.unwrap_or(0), profile, &app_handle);
+    
+    result_rx.await
+        .unwrap_or_else(|_| Err(OllamaError::Other("Download was removed from the queue".to_string())))
+}
+
+// How a pull ended when it did not fail
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PullOutcome {
+    // Ollama reported success
+    Completed,
+    // Paused or cancelled, the progress is kept for a resume
+    Stopped,
+}
+
+impl PullOutcome {
+    fn message(&self) -> &'static str {
+        match self {
+            PullOutcome::Completed => "Model download completed successfully",
+            PullOutcome::Stopped => "Download cancelled by user",
+        }
+    }
+}
+
+// Run a pull from the queue, reissuing /api/pull after transient failures
+// so Ollama resumes from the blobs it already has
+async fn run_pull_with_retry(model_name: String, channel_id: String, profile: Option<String>, app_handle: tauri::AppHandle) -> Result<PullOutcome, OllamaError> {
+    let policy = PullRetryPolicy::default();
+    let mut attempt = 1;
+    // Most bytes any attempt reached, an attempt that gets further earns a fresh retry budget
+    let mut best_bytes = downloaded_bytes(&channel_id);
+    
+    loop {
+        match run_pull(model_name.clone(), channel_id.clone(), profile.as_deref(), app_handle.clone()).await {
+            Err(err) if err.is_transient() => {
+                let bytes = downloaded_bytes(&channel_id);
+                if bytes > best_bytes {
+                    println!("[DOWNLOAD] Pull made progress before failing, retry budget reset: model={}, channel_id={}, completed_bytes={}", 
+                             model_name, channel_id, bytes);
+                    best_bytes = bytes;
+                    attempt = 1;
+                }
+                if attempt >= policy.max_attempts {
+                    return Err(err);
+                }
+                
+                let delay = policy.delay(attempt);
+                attempt += 1;
+                println!("[DOWNLOAD] Pull interrupted, retrying: model={}, channel_id={}, attempt={}/{}, delay_ms={}, error={}", 
+                         model_name, channel_id, attempt, policy.max_attempts, delay.as_millis(), err);
+                
+                let _ = app_handle.emit_all(&channel_id, PullModelResponse {
+                    status: "retrying".to_string(),
+                    digest: None,
+                    total: None,
+                    completed: None,
+                    progress: None,
+                    window: None,
+                    verification: None,
+                    retry: Some(PullRetryInfo {
+                        attempt,
+                        max_attempts: policy.max_attempts,
+                        retry_in_ms: delay.as_millis() as u64,
+                        error: err.to_string(),
+                    }),
+                });
+                
+                if !wait_before_retry(&channel_id, delay).await {
+                    println!("[DOWNLOAD] Download stopped while waiting to retry: model={}, channel_id={}", model_name, channel_id);
+                    return Ok(PullOutcome::Stopped);
+                }
+            }
+            result => return result,
+        }
+    }
+}
+
+// Bytes the download has reached, live while it runs and from the progress file before the first attempt
+fn downloaded_bytes(channel_id: &str) -> i64 {
+    let live = ACTIVE_DOWNLOADS.lock().unwrap().get(channel_id).map(|state| state.completed_bytes);
+    live.or_else(|| load_download_progress(channel_id).map(|progress| progress.completed_bytes))
+        .unwrap_or(0)
+}
+
+// Wait out a retry backoff, returns false if the download was paused or cancelled meanwhile
+async fn wait_before_retry(channel_id: &str, delay: std::time::Duration) -> bool {
+    // Swap in a fresh cancel channel, the previous receiver ended with the failed attempt
+    let (cancel_tx, cancel_rx) = oneshot::channel::<()>();
+    match ACTIVE_DOWNLOADS.lock().unwrap().get_mut(channel_id) {
+        Some(download_state) => download_state.cancel_tx = cancel_tx,
+        None => return false,
+    }
+    
+    tokio::select! {
+        _ = cancel_rx => false,
+        _ = tokio::time::sleep(delay) => true,
+    }
+}
+
+// Run a single pull attempt, called by the download queue once a slot is free
+async fn run_pull(model_name: String, channel_id: String, profile: Option<&str>, app_handle: tauri::AppHandle) -> Result<PullOutcome, OllamaError> {
+    let client = get_client(profile)?;
+    println!("[PULL_MODEL] Using Ollama server address: {}", client.base_url());
     
     // Check for any existing download state (previous progress)
     // First check active downloads, then check persistent storage
-    let completed_bytes = {
+    let previous_bytes = {
         let active_downloads = ACTIVE_DOWNLOADS.lock().unwrap();
         if let Some(download_state) = active_downloads.get(&channel_id) {
             println!("[DOWNLOAD] Found active download state: model={}, channel_id={}, completed_bytes={}", 
                      model_name, channel_id, download_state.completed_bytes);
-            Some(download_state.completed_bytes)
+            Some((download_state.completed_bytes, download_state.total_bytes))
         } else {
             // Check persistent storage for previous download progress
             if let Some(progress) = load_download_progress(&channel_id) {
-                println!("[DOWNLOAD] Restore download progress from persistent storage: model={}, channel_id={}, completed_bytes={}", 
-                         model_name, channel_id, progress.completed_bytes);
-                Some(progress.completed_bytes)
+                println!("[DOWNLOAD] Restore download progress from persistent storage: model={}, channel_id={}, completed_bytes={}, total_bytes={}", 
+                         model_name, channel_id, progress.completed_bytes, progress.total_bytes);
+                Some((progress.completed_bytes, progress.total_bytes))
             } else {
                 println!("[DOWNLOAD] No existing download state found, starting fresh download: model={}, channel_id={}", 
                          model_name, channel_id);
@@ -599,29 +1403,21 @@ pub async fn pull_model(model_name: String, channel_id: String, app_handle: taur
             }
         }
     };
+    let completed_bytes = previous_bytes.map(|(completed, _)| completed);
     
     // Create a oneshot channel for cancellation
     let (cancel_tx, mut cancel_rx) = oneshot::channel::<()>();
     
-    // Always use streaming for model pulls to get progress updates
-    let payload = json!({
-        "name": model_name.clone(),
-        "stream": true
-    });
-    
     // Store the new download state
     {
         let mut active_downloads = ACTIVE_DOWNLOADS.lock().unwrap();
         active_downloads.insert(channel_id.clone(), DownloadState {
             cancel_tx,
             completed_bytes: completed_bytes.unwrap_or(0),
+            total_bytes: previous_bytes.map(|(_, total)| total).unwrap_or(0),
         });
     }
 
-    // Create a separate task for the actual download
-    let client = get_client();
-    let url = format!("{}/api/pull", base_url);
-    
     // Note: Ollama API does not support HTTP Range requests for resumable downloads
     // We rely on Ollama's own incremental download mechanism
     if let Some(bytes) = completed_bytes {
@@ -633,11 +1429,8 @@ pub async fn pull_model(model_name: String, channel_id: String, app_handle: taur
     }
     
     // Send request to the Ollama server (without Range headers)
-    let response = client.post(&url)
-        .json(&payload)
-        .timeout(Duration::from_secs(600)) // Longer timeout for model downloads
-        .send().await
-        .map_err(|e| format!("Failed to pull model: {}", e))?;
+    // Always use streaming for model pulls to get progress updates
+    let response = client.pull_stream(&model_name).await?;
     
     // Log the response status and headers for debugging
     println!("[DOWNLOAD] Server response status: model={}, channel_id={}, status={}", 
@@ -657,11 +1450,8 @@ pub async fn pull_model(model_name: String, channel_id: String, app_handle: taur
     }
         
     if !response.status().is_success() {
-        // Clean up our download state
-        let mut active_downloads = ACTIVE_DOWNLOADS.lock().unwrap();
-        active_downloads.remove(&channel_id);
-        
-        return Err(format!("HTTP error: {}", response.status()));
+        // The download state stays for a retry, the queue removes it when the download ends
+        return Err(OllamaError::from_response(response).await);
     }
     
     // Use unique event name provided by frontend
@@ -673,6 +1463,11 @@ pub async fn pull_model(model_name: String, channel_id: String, app_handle: taur
     // Use byte stream reading method
     let mut stream = response.bytes_stream();
     
+    // Ollama reports progress per layer, aggregate it so the overall bytes never go backwards
+    let mut progress_tracker = PullProgressTracker::new();
+    // The request has no total timeout, a stream that stays silent too long counts as stalled
+    let mut last_data = std::time::Instant::now();
+    
     // Process response stream, parse each line of JSON and send to frontend
     loop {
         // Efficiently check for cancellation signal
@@ -691,7 +1486,7 @@ pub async fn pull_model(model_name: String, channel_id: String, app_handle: taur
                          model_name, channel_id);
             }
             
-            return Ok("Download cancelled by user".to_string());
+            return Ok(PullOutcome::Stopped);
         }
         
         // Use timeout mechanism to get next data chunk, ensuring timely response to cancellation requests
@@ -699,8 +1494,14 @@ pub async fn pull_model(model_name: String, channel_id: String, app_handle: taur
             std::time::Duration::from_millis(100), 
             stream.next()
         ).await {
-            Ok(Some(result)) => result,
+            Ok(Some(result)) => {
+                last_data = std::time::Instant::now();
+                result
+            }
             Ok(None) => break, // End of stream
+            Err(_) if last_data.elapsed() >= PULL_IDLE_TIMEOUT => {
+                return Err(OllamaError::Timeout(format!("No progress from Ollama for {} seconds", PULL_IDLE_TIMEOUT.as_secs())));
+            }
             Err(_) => continue, // Timeout, check cancellation again
         };
         
@@ -721,6 +1522,13 @@ pub async fn pull_model(model_name: String, channel_id: String, app_handle: taur
                             
                             // Parse JSON response
                             if let Ok(json_value) = serde_json::from_str::<Value>(line) {
+                                // Ollama reports failures such as an unknown tag after the 200 status as an error line
+                                if let Some(error) = json_value["error"].as_str() {
+                                    println!("[DOWNLOAD] Pull failed on the server: model={}, channel_id={}, error={}", 
+                                             model_name, channel_id, error);
+                                    return Err(OllamaError::Other(error.to_string()));
+                                }
+                                
                                 let status = json_value["status"].as_str().unwrap_or("unknown").to_string();
                                 
                                 // Extract key information
@@ -728,18 +1536,21 @@ pub async fn pull_model(model_name: String, channel_id: String, app_handle: taur
                                 let total = json_value["total"].as_i64();
                                 let completed = json_value["completed"].as_i64();
                                 
-                                // Update completed bytes in download state
-                                if let Some(completed_value) = completed {
+                                // Update overall bytes in download state
+                                if let Some(layer_digest) = &digest {
+                                    progress_tracker.update(layer_digest, total, completed, std::time::Instant::now());
+                                    let completed_value = progress_tracker.completed_bytes();
+                                    
                                     let mut active_downloads = ACTIVE_DOWNLOADS.lock().unwrap();
                                     if let Some(download_state) = active_downloads.get_mut(&channel_id) {
                                         // Only print logs when there are significant changes to avoid too many logs
-                                        if completed_value % 10_000_000 == 0 || 
-                                           (download_state.completed_bytes / 10_000_000) != (completed_value / 10_000_000) {
+                                        if (download_state.completed_bytes / 100_000_000) != (completed_value / 100_000_000) {
                                             println!("[DOWNLOAD] Update download progress: model={}, channel_id={}, completed_bytes={}, total={}", 
                                                      model_name, channel_id, completed_value, 
-                                                     total.unwrap_or(-1));
+                                                     progress_tracker.total_bytes());
                                         }
                                         download_state.completed_bytes = completed_value;
+                                        download_state.total_bytes = progress_tracker.total_bytes();
                                     }
                                 }
                                 
@@ -749,6 +1560,10 @@ pub async fn pull_model(model_name: String, channel_id: String, app_handle: taur
                                     digest,
                                     total,
                                     completed,
+                                    progress: Some(progress_tracker.summary()).filter(|p| p.layer_count > 0),
+                                    retry: None,
+                                    window: None,
+                                    verification: None,
                                 };
                                 
                                 // Send progress events to frontend
@@ -757,11 +1572,8 @@ pub async fn pull_model(model_name: String, channel_id: String, app_handle: taur
                                 // If status is "success", download is complete
                                 if status == "success" {
                                     println!("Model {} download completed successfully", model_name);
-                                    // Remove from active downloads
-                                    let mut active_downloads = ACTIVE_DOWNLOADS.lock().unwrap();
-                                    active_downloads.remove(&channel_id);
-                                    
-                                    return Ok("Model download completed successfully".to_string());
+                                    // The queue removes the download state once it has recorded the result
+                                    return Ok(PullOutcome::Completed);
                                 }
                             } else {
                                 // Tolerant handling of JSON parsing errors - consistent with frontend implementation
@@ -779,51 +1591,24 @@ pub async fn pull_model(model_name: String, channel_id: String, app_handle: taur
                 println!("Error reading stream for model {}: {}", model_name, err);
                 
                 // Keep the download state with current progress
-                return Err(format!("Failed to read model data: {}", err));
+                return Err(err.into());
             }
         }
     }
     
-    // If we reached the end of the stream but didn't receive a success status
+    // Only a success line means the model is complete, the download state stays for a retry
     println!("Model {} download stream ended without explicit success message", model_name);
-    
-    // On normal stream end, clean up resources
-    let mut active_downloads = ACTIVE_DOWNLOADS.lock().unwrap();
-    if let Some(download_state) = active_downloads.get(&channel_id) {
-        println!("Stream ended normally, completed bytes: {}", download_state.completed_bytes);
-    }
-    
-    // Remove download state as it's completed
-    active_downloads.remove(&channel_id);
-    
-    // We assume download completed successfully (consistent with frontend's tolerant approach)
-    Ok("Model download completed".to_string())
+    Err(OllamaError::Interrupted(format!("Pull of {} ended before Ollama reported success", model_name)))
 }
 
 #[command]
-pub async fn cancel_pull(model_name: String, channel_id: String, cleanup: bool) -> Result<bool, String> {
+pub async fn cancel_pull(model_name: String, channel_id: String, cleanup: bool, app_handle: tauri::AppHandle) -> Result<bool, OllamaError> {
     println!("[CANCEL] Start cancelling download: model={}, channel_id={}, cleanup={}", model_name, channel_id, cleanup);
     
-    let mut active_downloads = ACTIVE_DOWNLOADS.lock().unwrap();
-    let state = active_downloads.remove(&channel_id);
-
-    let mut result = false;
-    if let Some(download_state) = state {
-        // Save the last known progress
-        save_download_progress(&channel_id, &DownloadProgress {
-                model_name: model_name.clone(),
-                channel_id: channel_id.clone(),
-            completed_bytes: download_state.completed_bytes,
-            total_bytes: 0, // Total is not known here, might need to update this logic if total is needed
-            last_updated: std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_secs(),
-        });
-        
-        if download_state.cancel_tx.send(()).is_ok() {
-            println!("[CANCEL] Cancel signal sent successfully: model={}, channel_id={}", model_name, channel_id);
-            result = true;
-    } else {
-            println!("[CANCEL] Failed to send cancel signal (receiver closed): model={}, channel_id={}", model_name, channel_id);
-        }
+    let result = stop_download(&channel_id, "Download cancelled by user");
+    if result {
+        println!("[CANCEL] Download stopped: model={}, channel_id={}", model_name, channel_id);
+        emit_download_queue(&app_handle);
     } else {
         println!("[CANCEL] No active download task found: model={}, channel_id={}", model_name, channel_id);
     }
@@ -837,34 +1622,55 @@ pub async fn cancel_pull(model_name: String, channel_id: String, cleanup: bool)
 }
 
 #[command]
-pub async fn push_model(model_name: String, insecure: bool) -> Result<String, String> {
-    let base_url = get_ollama_host().map_err(|e| e.to_string())?;
-    
-    let payload = json!({
-        "model": model_name,
-        "stream": false,
-        "insecure": insecure
-    });
-    
-    let client = get_client();
-    let url = format!("{}/api/push", base_url);
-    
-    let response = client.post(&url)
-        .json(&payload)
-        .send().await
-        .map_err(|e| format!("Failed to push model: {}", e))?;
-        
-    if !response.status().is_success() {
-        return Err(format!("HTTP error: {}", response.status()));
+pub async fn get_download_queue() -> Result<Vec<QueueEntry>, OllamaError> {
+    Ok(DOWNLOAD_QUEUE.lock().unwrap().snapshot())
+}
+
+#[command]
+pub async fn move_download(channel_id: String, position: usize, app_handle: tauri::AppHandle) -> Result<bool, OllamaError> {
+    let moved = DOWNLOAD_QUEUE.lock().unwrap().move_to(&channel_id, position);
+    if moved {
+        println!("[QUEUE] Move download: channel_id={}, position={}", channel_id, position);
+        download_queue_changed(&app_handle);
+    }
+    Ok(moved)
+}
+
+#[command]
+pub async fn set_download_priority(channel_id: String, priority: i32, app_handle: tauri::AppHandle) -> Result<bool, OllamaError> {
+    let updated = DOWNLOAD_QUEUE.lock().unwrap().set_priority(&channel_id, priority);
+    if updated {
+        println!("[QUEUE] Set download priority: channel_id={}, priority={}", channel_id, priority);
+        download_queue_changed(&app_handle);
+    }
+    Ok(updated)
+}
+
+/// Push a model to its registry, streaming per-layer progress to `channel_id`
+///
+/// The channel id doubles as the request id for cancel_generation.
+#[command]
+pub async fn push_model(model_name: String, insecure: bool, channel_id: String, profile: Option<String>, app_handle: tauri::AppHandle) -> Result<String, OllamaError> {
+    let (mut cancel_rx, _guard) = register_generation(&channel_id)?;
+    let client = get_client(profile.as_deref())?;
+    
+    tokio::select! {
+        _ = &mut cancel_rx => {
+            println!("[PUSH] Push cancelled: model={}, channel_id={}", model_name, channel_id);
+            Err(OllamaError::Cancelled)
+        }
+        result = async {
+            let response = client.push_stream(&model_name, insecure).await?;
+            forward_status_stream(response, &channel_id, &app_handle).await
+        } => {
+            result?;
+            Ok("Model pushed successfully".to_string())
+        }
     }
-    
-    Ok("Model pushed successfully".to_string())
 }
 
 #[tauri::command]
-pub async fn validate_host(host: String) -> Result<bool, String> {
-    let client = get_client();
-    
+pub async fn validate_host(host: String) -> Result<bool, OllamaError> {
     // Normalize host URL if needed
     let host_url = if !host.starts_with("http://") && !host.starts_with("https://") {
         format!("http://{}" , host)
@@ -879,26 +1685,21 @@ pub async fn validate_host(host: String) -> Result<bool, String> {
         host_url
     };
     
-    // Test connection by trying to access the version endpoint
-    match client
-        .get(&format!("{}/api/version", host_url))
-        .timeout(Duration::from_secs(5))
-        .send()
-        .await {
-            Ok(response) => Ok(response.status().is_success()),
-            Err(_) => Ok(false), // Connection failed, host is invalid
+    // Test connection by trying to access the version endpoint, with the credentials stored for the host
+    let client = match crate::config_manager::host_server(&host_url) {
+        Ok(server) => OllamaClient::from_server(&server)?,
+        // Checking a plain address does not need the config, so go on without stored credentials
+        Err(e) => {
+            println!("[VALIDATE] Could not read the stored credentials for {}: {}", host_url, e);
+            OllamaClient::new(host_url)?
         }
+    };
+    client.is_reachable().await
 }
 
 #[tauri::command]
-pub async fn generate_chat_completion(request: ChatRequest, app_handle: tauri::AppHandle) -> Result<String, String> {
-    let client = get_client();
-    
-    // Use existing get_ollama_host to get Ollama host address
-    let host = get_ollama_host().map_err(|e| format!("Failed to get Ollama host: {}", e))?;
-    
-    // Build complete URL
-    let url = format!("{}/api/chat", host);
+pub async fn generate_chat_completion(request: ChatRequest, request_id: Option<String>, profile: Option<String>, app_handle: tauri::AppHandle) -> Result<String, OllamaError> {
+    let client = get_client(profile.as_deref())?;
     
     // Create a new request object, keeping original stream settings
     let mut ollama_request = request.clone();
@@ -906,90 +1707,108 @@ pub async fn generate_chat_completion(request: ChatRequest, app_handle: tauri::A
     let should_stream = request.stream.unwrap_or(false);
 
     if should_stream {
+        // Requests with an id can be stopped through cancel_generation
+        let registration = match &request_id {
+            Some(id) => Some(register_generation(id)?),
+            None => None,
+        };
+        let (cancel_rx, _guard) = match registration {
+            Some((cancel_rx, guard)) => (Some(cancel_rx), Some(guard)),
+            None => (None, None),
+        };
+        let cancelled = async move {
+            match cancel_rx {
+                Some(cancel_rx) => { let _ = cancel_rx.await; }
+                None => std::future::pending::<()>().await,
+            }
+        };
+        tokio::pin!(cancelled);
+        
         // Enable streaming
         ollama_request.stream = Some(true);
         
         // Send request and get streaming response
-        let response = client.post(&url)
-            .json(&ollama_request)
-            .timeout(Duration::from_secs(120)) // Increase timeout, streaming responses may need more time
-            .send()
-            .await
-            .map_err(|e| format!("Failed to generate chat completion: {}", e))?;
-            
-        if !response.status().is_success() {
-            return Err(format!("HTTP error: {}", response.status()));
-        }
+        let response = client.chat_stream(&ollama_request).await?;
         
         // Handle streaming response
         let mut full_response = String::new();
-        let mut buffer = String::new();
+        let mut buffer: Vec<u8> = Vec::new();
         
-        // Create event name
-        let event_name = "ollama-chat-stream";
+        // Events go to the request's own channel when an id is given, otherwise to the shared chat event
+        let event_name = request_id.as_deref().unwrap_or("ollama-chat-stream");
         
         // Use byte stream reading method
         let mut stream = response.bytes_stream();
         
         // Parse JSON line by line and send events
-        while let Some(chunk_result) = stream.next().await {
-            match chunk_result {
-                Ok(bytes) => {
-                    // Convert bytes to string and add to buffer
-                    if let Ok(chunk_str) = String::from_utf8(bytes.to_vec()) {
-                        buffer.push_str(&chunk_str);
+        loop {
+            let chunk_result = tokio::select! {
+                _ = &mut cancelled => {
+                    // Dropping the stream closes the HTTP connection, which stops generation in Ollama
+                    println!("[CHAT] Generation cancelled: request_id={}", event_name);
+                    let _ = app_handle.emit_all(event_name, StreamResponse {
+                        content: String::new(),
+                        done: true,
+                        role: None,
+                        thinking: None,
+                        tool_calls: None,
+                        done_reason: Some(DONE_REASON_CANCELLED.to_string()),
+                        metrics: None,
+                    });
+                    return Err(OllamaError::Cancelled);
+                }
+                chunk = stream.next() => match chunk {
+                    Some(chunk_result) => chunk_result,
+                    None => break, // End of stream
+                },
+            };
+            
+            let bytes = chunk_result?;
+            buffer.extend_from_slice(&bytes);
+            
+            for line in drain_complete_lines(&mut buffer) {
+                // Try to parse as ChatResponse
+                match serde_json::from_str::<ChatResponse>(&line) {
+                    Ok(chat_response) => {
+                        let message = chat_response.message;
+                        let thinking = message.thinking.filter(|t| !t.is_empty());
+                        let tool_calls = message.tool_calls.filter(|calls| !calls.is_empty());
                         
-                        // Process complete lines from buffer
-                        while let Some(newline_pos) = buffer.find('\n') {
-                            // Extract the part before newline into a new String
-                            let before_newline = buffer[..newline_pos].to_string();
-                            // Get the part after newline
-                            let after_newline = buffer[newline_pos + 1..].to_string();
-                            // Clear and reset the buffer with only what's after the newline
-                            buffer.clear();
-                            buffer.push_str(&after_newline);
-                            
-                            // Now safely trim the line
-                            let line = before_newline.trim();
-                            if line.is_empty() {
-                                continue;
-                            }
+                        // Only send incremental content, not accumulated.
+                        // Reasoning and tool call chunks often have empty content, forward them too
+                        if !message.content.is_empty() || thinking.is_some() || tool_calls.is_some() {
+                            let _ = app_handle.emit_all(event_name, StreamResponse {
+                                content: message.content.clone(),
+                                done: false,
+                                role: Some(message.role.clone()),
+                                thinking,
+                                tool_calls,
+                                done_reason: None,
+                                metrics: None,
+                            });
                             
-                            // Try to parse as ChatResponse
-                            match serde_json::from_str::<ChatResponse>(line) {
-                                Ok(chat_response) => {
-                                    let content = chat_response.message.content.clone();
-                                    
-                                    // Only send incremental content, not accumulated
-                                    if !content.is_empty() {
-                                        let _ = app_handle.emit_all(event_name, StreamResponse {
-                                            content: content.clone(),
-                                            done: false,
-                                        });
-                                        
-                                        // Accumulate complete response
-                                        full_response.push_str(&content);
-                                    }
-                                    
-                                    // If done, send final event and break
-                                    if chat_response.done {
-                                        let _ = app_handle.emit_all(event_name, StreamResponse {
-                                            content: String::new(), // Empty content for done event
-                                            done: true,
-                                        });
-                                        return Ok(full_response);
-                                    }
-                                },
-                                Err(e) => {
-                                    // Parse error, log but continue processing
-                                    eprintln!("Error parsing stream JSON: {}, raw: {}", e, line);
-                                }
-                            }
+                            // Accumulate complete response
+                            full_response.push_str(&message.content);
                         }
+                        
+                        // If done, send final event and break
+                        if chat_response.done {
+                            let _ = app_handle.emit_all(event_name, StreamResponse {
+                                content: String::new(), // Empty content for done event
+                                done: true,
+                                role: Some(message.role),
+                                thinking: None,
+                                tool_calls: None,
+                                done_reason: chat_response.done_reason,
+                                metrics: Some(chat_response.metrics.with_throughput()),
+                            });
+                            return Ok(full_response);
+                        }
+                    },
+                    Err(e) => {
+                        // Parse error, log but continue processing
+                        eprintln!("Error parsing stream JSON: {}, raw: {}", e, line);
                     }
-                },
-                Err(e) => {
-                    return Err(format!("Stream error: {}", e));
                 }
             }
         }
@@ -999,62 +1818,420 @@ pub async fn generate_chat_completion(request: ChatRequest, app_handle: tauri::A
         // Non-streaming processing, keep original behavior
         ollama_request.stream = Some(false);
         
-        let response = client.post(&url)
-            .json(&ollama_request)
-            .timeout(Duration::from_secs(60))
-            .send()
-            .await
-            .map_err(|e| format!("Failed to generate chat completion: {}", e))?;
+        let chat_response = client.chat(&ollama_request).await?;
             
-        if !response.status().is_success() {
-            return Err(format!("HTTP error: {}", response.status()));
+        Ok(chat_response.message.content)
+    }
+}
+
+#[command]
+pub async fn chat_with_tools(request: ChatRequest, channel_id: String, allowed_dirs: Option<Vec<String>>, max_steps: Option<usize>, profile: Option<String>, app_handle: tauri::AppHandle) -> Result<ChatMessage, OllamaError> {
+    // Tool calls for caller-defined tools could not be executed here
+    if request.tools.as_ref().is_some_and(|tools| !tools.is_empty()) {
+        return Err(OllamaError::Other(
+            "chat_with_tools only offers the built-in tools, leave `tools` out of the request".to_string(),
+        ));
+    }
+    
+    // The channel id doubles as the request id for cancel_generation
+    let (mut cancel_rx, _guard) = register_generation(&channel_id)?;
+    let client = get_client(profile.as_deref())?;
+    let registry = ToolRegistry::new(client.clone(), &allowed_dirs.unwrap_or_default());
+    
+    // Each step is a complete (non-streaming) turn so tool calls arrive whole
+    let mut chat_request = request;
+    chat_request.stream = Some(false);
+    chat_request.tools = Some(registry.definitions());
+    
+    let max_steps = max_steps.unwrap_or(DEFAULT_MAX_TOOL_STEPS);
+    let cancelled = |step: usize| {
+        println!("[TOOLS] Tool chat cancelled: channel_id={}, step={}", channel_id, step);
+        let _ = app_handle.emit_all(&channel_id, ToolChatEvent::Done {
+            step,
+            content: String::new(),
+            done_reason: Some(DONE_REASON_CANCELLED.to_string()),
+            metrics: None,
+        });
+        OllamaError::Cancelled
+    };
+    
+    for step in 0..max_steps {
+        let chat_response = tokio::select! {
+            _ = &mut cancel_rx => return Err(cancelled(step)),
+            result = client.chat(&chat_request) => result?,
+        };
+        
+        let message = chat_response.message;
+        let _ = app_handle.emit_all(&channel_id, ToolChatEvent::Assistant {
+            step,
+            message: message.clone(),
+        });
+        
+        let tool_calls: Vec<ToolCall> = message.tool_calls.iter()
+            .flatten()
+            .filter_map(ToolCall::from_value)
+            .collect();
+        
+        // No tool calls means the model gave its final answer
+        if tool_calls.is_empty() {
+            let _ = app_handle.emit_all(&channel_id, ToolChatEvent::Done {
+                step,
+                content: message.content.clone(),
+                done_reason: chat_response.done_reason,
+                metrics: Some(chat_response.metrics),
+            });
+            return Ok(message);
         }
         
-        let chat_response = response.json::<ChatResponse>()
-            .await
-            .map_err(|e| format!("Failed to parse response: {}", e))?;
+        chat_request.messages.push(message);
+        
+        for call in tool_calls {
+            println!("[TOOLS] Running tool: channel_id={}, step={}, tool={}", channel_id, step, call.name);
+            let result = tokio::select! {
+                _ = &mut cancel_rx => return Err(cancelled(step)),
+                result = registry.execute(&call) => result,
+            };
+            // Tool failures are reported back to the model instead of aborting the loop
+            let (content, is_error) = match result {
+                Ok(content) => (content, false),
+                Err(e) => (e, true),
+            };
             
-        Ok(chat_response.message.content)
+            let _ = app_handle.emit_all(&channel_id, ToolChatEvent::ToolResult {
+                step,
+                name: call.name.clone(),
+                arguments: call.arguments.clone(),
+                content: content.clone(),
+                is_error,
+            });
+            
+            chat_request.messages.push(ChatMessage {
+                role: "tool".to_string(),
+                content,
+                thinking: None,
+                images: None,
+                tool_calls: None,
+                tool_name: Some(call.name),
+            });
+        }
     }
+    
+    Err(OllamaError::Other(format!("Model did not give a final answer within {} tool steps", max_steps)))
 }
 
 #[command]
-#[allow(dead_code)]
-pub async fn pause_pull(channel_id: String) -> Result<bool, String> {
+pub async fn generate_structured(request: ChatRequest, schema: Value, max_retries: Option<u32>, profile: Option<String>) -> Result<StructuredResponse, OllamaError> {
+    let client = get_client(profile.as_deref())?;
+    
+    // Ollama constrains the output with the schema passed as format
+    let mut chat_request = request;
+    chat_request.stream = Some(false);
+    chat_request.format = Some(schema.clone());
+    
+    let max_attempts = max_retries.unwrap_or(0).min(MAX_STRUCTURED_RETRIES) + 1;
+    let mut attempt = 0;
+    
+    loop {
+        attempt += 1;
+        let chat_response = client.chat(&chat_request).await?;
+        let raw = chat_response.message.content.clone();
+        
+        let (value, errors) = match serde_json::from_str::<Value>(raw.trim()) {
+            Ok(value) => {
+                let errors = json_schema::validate(&schema, &value);
+                (Some(value), errors)
+            }
+            Err(e) => (None, vec![SchemaError {
+                path: String::new(),
+                message: format!("output is not valid JSON: {}", e),
+            }]),
+        };
+        
+        if errors.is_empty() || attempt >= max_attempts {
+            println!("[STRUCTURED] Finished: model={}, attempts={}, errors={}", chat_request.model, attempt, errors.len());
+            return Ok(StructuredResponse {
+                valid: errors.is_empty(),
+                value,
+                raw,
+                attempts: attempt,
+                errors,
+                metrics: chat_response.metrics,
+            });
+        }
+        
+        // Feed the errors back so the model can correct its previous answer
+        println!("[STRUCTURED] Output failed validation, retrying: model={}, attempt={}, errors={}", chat_request.model, attempt, errors.len());
+        chat_request.messages.push(chat_response.message);
+        chat_request.messages.push(ChatMessage {
+            role: "user".to_string(),
+            content: format!(
+                "Your previous answer does not match the required JSON schema:\n{}\nReply again with only the corrected JSON.",
+                json_schema::describe_errors(&errors)
+            ),
+            thinking: None,
+            images: None,
+            tool_calls: None,
+            tool_name: None,
+        });
+    }
+}
+
+#[command]
+pub async fn cancel_generation(request_id: String) -> Result<bool, OllamaError> {
+    println!("[CANCEL] Cancelling generation: request_id={}", request_id);
+    
+    let cancel_tx = ACTIVE_GENERATIONS.lock().unwrap().remove(&request_id);
+    match cancel_tx {
+        Some(cancel_tx) => Ok(cancel_tx.send(()).is_ok()),
+        None => {
+            println!("[CANCEL] No active generation found: request_id={}", request_id);
+            Ok(false)
+        }
+    }
+}
+
+#[command]
+pub async fn pause_pull(channel_id: String, app_handle: tauri::AppHandle) -> Result<bool, OllamaError> {
     println!("[DOWNLOAD] Attempting to pause download for channel_id: {}", channel_id);
-    let mut active_downloads = ACTIVE_DOWNLOADS.lock().unwrap();
-    if let Some(state) = active_downloads.remove(&channel_id) {
-        let _ = state.cancel_tx.send(());
+    if stop_download(&channel_id, "Download paused by user") {
         println!("[DOWNLOAD] Paused download for channel_id: {}", channel_id);
+        emit_download_queue(&app_handle);
         Ok(true)
     } else {
-        eprintln!("[DOWNLOAD] No active download found for channel_id to pause: {}", channel_id);
+        eprintln!("[DOWNLOAD] No queued or active download found for channel_id to pause: {}", channel_id);
         Ok(false)
     }
 }
 
 #[command]
-#[allow(dead_code)]
-pub async fn resume_pull(model_name: String, channel_id: String, app_handle: tauri::AppHandle) -> Result<String, String> {
+pub async fn resume_pull(model_name: String, channel_id: String, profile: Option<String>, app_handle: tauri::AppHandle) -> Result<String, OllamaError> {
     println!("[DOWNLOAD] Attempting to resume download for model: {}, channel_id: {}", model_name, channel_id);
-    // Directly call pull_model, it will handle resume logic
-    pull_model(model_name, channel_id, app_handle).await
+    // Re-enqueue with the saved priority and server, pull_model restores the saved bytes
+    let saved = load_download_progress(&channel_id);
+    let priority = saved.as_ref().map(|p| p.priority);
+    let profile = profile.or_else(|| saved.and_then(|p| p.profile));
+    pull_model(model_name, channel_id, priority, profile, app_handle).await
 }
 
 #[command]
-#[allow(dead_code)]
-pub async fn check_download_status(channel_id: String) -> Result<Option<DownloadProgress>, String> {
-    let progress_map = DOWNLOAD_PROGRESS.lock().unwrap();
-    if let Some(progress) = progress_map.get(&channel_id) {
-        // Additional check: is it still actively downloading?
-        let active_downloads = ACTIVE_DOWNLOADS.lock().unwrap();
-        if active_downloads.contains_key(&channel_id) {
-            Ok(Some(progress.clone()))
-        } else {
-            // If not in active_downloads, it might be paused or finished
-            Ok(Some(progress.clone()))
+pub async fn check_download_status(channel_id: String) -> Result<Option<DownloadProgress>, OllamaError> {
+    let queue_entry = DOWNLOAD_QUEUE.lock().unwrap().get(&channel_id).cloned();
+    let saved = load_download_progress(&channel_id);
+    let live_bytes = ACTIVE_DOWNLOADS.lock().unwrap()
+        .get(&channel_id)
+        .map(|state| (state.completed_bytes, state.total_bytes));
+    
+    Ok(merge_download_status(saved, queue_entry, live_bytes))
+}
+
+// Combine the saved record with the live queue state, the queue is authoritative for queued and active downloads
+fn merge_download_status(saved: Option<DownloadProgress>, queue_entry: Option<QueueEntry>, live_bytes: Option<(i64, i64)>) -> Option<DownloadProgress> {
+    let mut progress = match (saved, &queue_entry) {
+        (Some(saved), _) => saved,
+        (None, Some(entry)) => DownloadProgress {
+            model_name: entry.model_name.clone(),
+            channel_id: entry.channel_id.clone(),
+            completed_bytes: 0,
+            total_bytes: 0,
+            last_updated: unix_timestamp(),
+            status: entry.status,
+            priority: entry.priority,
+            position: 0,
+            enqueued_at: entry.enqueued_at,
+            profile: entry.profile.clone(),
+        },
+        (None, None) => return None,
+    };
+    
+    if let Some(entry) = queue_entry {
+        progress.status = entry.status;
+        progress.priority = entry.priority;
+    }
+    if let Some((completed_bytes, total_bytes)) = live_bytes {
+        progress.completed_bytes = completed_bytes;
+        progress.total_bytes = total_bytes;
+    }
+    
+    Some(progress)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_drain_complete_lines_keeps_partial_line() {
+        let mut buffer = b"{\"a\":1}\n\n{\"b\":2}\n{\"c\"".to_vec();
+        let lines = drain_complete_lines(&mut buffer);
+        assert_eq!(lines, vec!["{\"a\":1}", "{\"b\":2}"]);
+        assert_eq!(buffer, b"{\"c\"");
+    }
+
+    #[test]
+    fn test_drain_complete_lines_multibyte_split() {
+        // "é" is split across two chunks
+        let mut buffer = vec![b'"', 0xC3];
+        assert!(drain_complete_lines(&mut buffer).is_empty());
+        buffer.extend_from_slice(&[0xA9, b'"', b'\n']);
+        assert_eq!(drain_complete_lines(&mut buffer), vec!["\"é\""]);
+    }
+
+    #[tokio::test]
+    async fn test_cancel_generation_signals_and_unregisters() {
+        let (cancel_rx, guard) = register_generation("test-cancel").unwrap();
+        // The same id can't be registered twice while in flight
+        assert!(register_generation("test-cancel").is_err());
+        
+        assert!(cancel_generation("test-cancel".to_string()).await.unwrap());
+        assert!(cancel_rx.await.is_ok());
+        drop(guard);
+        
+        // Nothing left to cancel, and the id can be reused
+        assert!(!cancel_generation("test-cancel".to_string()).await.unwrap());
+        let (_cancel_rx, _guard) = register_generation("test-cancel").unwrap();
+    }
+
+    // Tests sharing the progress file run one at a time
+    static PROGRESS_FILE_LOCK: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+    
+    // Lock the progress file, which lives in a temp dir so the user's real download state is untouched
+    async fn lock_progress_file() -> tokio::sync::MutexGuard<'static, ()> {
+        let dir = std::env::temp_dir().join(format!("ollama-pro-test-{}", std::process::id()));
+        let _ = PROGRESS_DIR.set(dir.clone());
+        assert_eq!(PROGRESS_DIR.get(), Some(&dir));
+        PROGRESS_FILE_LOCK.lock().await
+    }
+
+    fn queue_entry(channel_id: &str, priority: i32, status: DownloadStatus) -> QueueEntry {
+        QueueEntry {
+            channel_id: channel_id.to_string(),
+            model_name: "llama3.2".to_string(),
+            priority,
+            profile: None,
+            status,
+            enqueued_at: 0,
+            run: 0,
         }
-    } else {
-        Ok(None)
+    }
+
+    #[tokio::test]
+    async fn test_pause_queued_download_keeps_saved_progress() {
+        let _lock = lock_progress_file().await;
+        let channel_id = "test-pause-queued";
+        // Bytes saved by an earlier pause, the download was resumed but has no slot yet
+        save_download_progress(channel_id, &DownloadProgress {
+            model_name: "llama3.2".to_string(),
+            channel_id: channel_id.to_string(),
+            completed_bytes: 100,
+            total_bytes: 1000,
+            last_updated: 0,
+            status: DownloadStatus::Queued,
+            priority: 3,
+            position: 0,
+            enqueued_at: 0,
+            profile: None,
+        });
+        DOWNLOAD_QUEUE.lock().unwrap().push_back(queue_entry(channel_id, 3, DownloadStatus::Queued));
+        let (result_tx, result_rx) = oneshot::channel();
+        DOWNLOAD_WAITERS.lock().unwrap().insert(channel_id.to_string(), vec![result_tx]);
+        
+        assert!(stop_download(channel_id, "Download paused by user"));
+        assert_eq!(result_rx.await.unwrap().unwrap(), "Download paused by user");
+        
+        let status = check_download_status(channel_id.to_string()).await.unwrap().unwrap();
+        assert_eq!(status.status, DownloadStatus::Paused);
+        assert_eq!((status.completed_bytes, status.total_bytes), (100, 1000));
+        assert_eq!(status.priority, 3);
+        
+        // Already paused, nothing left to stop
+        assert!(!stop_download(channel_id, "Download paused by user"));
+        clear_download_progress(channel_id);
+    }
+
+    #[tokio::test]
+    async fn test_pause_active_download_signals_and_saves_bytes() {
+        let _lock = lock_progress_file().await;
+        let channel_id = "test-pause-active";
+        DOWNLOAD_QUEUE.lock().unwrap().push_back(queue_entry(channel_id, 0, DownloadStatus::Active));
+        let (cancel_tx, cancel_rx) = oneshot::channel();
+        ACTIVE_DOWNLOADS.lock().unwrap().insert(channel_id.to_string(), DownloadState {
+            cancel_tx,
+            completed_bytes: 500,
+            total_bytes: 2000,
+        });
+        
+        let status = check_download_status(channel_id.to_string()).await.unwrap().unwrap();
+        assert_eq!(status.status, DownloadStatus::Active);
+        assert_eq!((status.completed_bytes, status.total_bytes), (500, 2000));
+        
+        assert!(stop_download(channel_id, "Download paused by user"));
+        assert!(cancel_rx.await.is_ok());
+        
+        let status = check_download_status(channel_id.to_string()).await.unwrap().unwrap();
+        assert_eq!(status.status, DownloadStatus::Paused);
+        assert_eq!((status.completed_bytes, status.total_bytes), (500, 2000));
+        clear_download_progress(channel_id);
+    }
+
+    #[test]
+    fn test_download_status_prefers_live_queue_state() {
+        let saved = DownloadProgress {
+            model_name: "llama3.2".to_string(),
+            channel_id: "test-merge".to_string(),
+            completed_bytes: 10,
+            total_bytes: 100,
+            last_updated: 0,
+            status: DownloadStatus::Failed,
+            priority: 0,
+            position: 0,
+            enqueued_at: 0,
+            profile: None,
+        };
+        
+        // A failed download stays failed until it is queued again
+        let status = merge_download_status(Some(saved.clone()), None, None).unwrap();
+        assert_eq!(status.status, DownloadStatus::Failed);
+        
+        let requeued = queue_entry("test-merge", 2, DownloadStatus::Queued);
+        let status = merge_download_status(Some(saved), Some(requeued), None).unwrap();
+        assert_eq!(status.status, DownloadStatus::Queued);
+        assert_eq!(status.priority, 2);
+        
+        assert!(merge_download_status(None, None, Some((1, 2))).is_none());
+    }
+
+    #[test]
+    fn test_chat_response_metrics_and_throughput() {
+        let line = r#"{"model":"llama3.2","created_at":"2024-07-22T20:33:28Z","message":{"role":"assistant","content":""},"done":true,"done_reason":"stop","total_duration":5191566416,"load_duration":2154458,"prompt_eval_count":26,"prompt_eval_duration":383809000,"eval_count":298,"eval_duration":4799921000}"#;
+        let chat_response: ChatResponse = serde_json::from_str(line).unwrap();
+        assert_eq!(chat_response.done_reason.as_deref(), Some("stop"));
+        
+        let metrics = chat_response.metrics.with_throughput();
+        assert_eq!(metrics.eval_count, Some(298));
+        assert_eq!(metrics.total_duration, Some(5191566416));
+        let tps = metrics.tokens_per_second.unwrap();
+        assert!((tps - 62.08).abs() < 0.01, "unexpected tokens/sec: {}", tps);
+        
+        // Intermediate chunks have no metrics
+        let chunk: ChatResponse = serde_json::from_str(r#"{"model":"llama3.2","message":{"role":"assistant","content":"Hi"},"done":false}"#).unwrap();
+        assert!(chunk.metrics.with_throughput().tokens_per_second.is_none());
+    }
+
+    #[test]
+    fn test_status_stream_requires_success_line() {
+        let mut reader = StatusStreamReader::new();
+        let events = reader.feed(b"{\"status\":\"pushing manifest\"}\n{\"status\":\"pushing\",\"digest\":\"sha256:aa\",\"total\":10,\"completed\":4}\n").unwrap();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[1].progress.as_ref().map(|p| p.layer_count), Some(1));
+        // Cut off before the success line
+        assert!(matches!(reader.finish(), Err(OllamaError::Interrupted(_))));
+        
+        let mut reader = StatusStreamReader::new();
+        reader.feed(b"{\"status\":\"writing manifest\"}\n{\"status\":\"succ").unwrap();
+        reader.feed(b"ess\"}\n").unwrap();
+        assert!(reader.finish().is_ok());
+        
+        let mut reader = StatusStreamReader::new();
+        assert!(matches!(reader.feed(b"{\"error\":\"model not found\"}\n"), Err(OllamaError::Other(_))));
     }
 }
@@ -0,0 +1,371 @@
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, AUTHORIZATION};
+use reqwest::{Certificate, Client, Response};
+use serde_json::{json, Value};
+use std::time::Duration;
+use crate::config_manager::{resolve_server, AuthScheme, ServerConnection};
+use crate::ollama_error::OllamaError;
+use crate::ollama_api::{
+    ChatRequest, ChatResponse, EmbeddingsRequest, EmbeddingsResponse, GenerateRequest,
+    GenerateResponse, ModelInfo, OllamaModel, OllamaModelList, OllamaRunningModel,
+    OllamaRunningModelList, OllamaVersion,
+};
+
+// Default timeout for regular (non-streaming) API requests
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(60);
+// Default timeout for streamed chats and generations
+const DEFAULT_STREAM_TIMEOUT: Duration = Duration::from_secs(600);
+// Upper bound for pulls, creates, pushes and blob uploads, which can run for a
+// long time on large models; stalls are caught by the caller's idle timeout instead
+const OPERATION_TIMEOUT: Duration = Duration::from_secs(24 * 60 * 60);
+// Timeout used for quick reachability checks
+const PING_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Reusable client for the Ollama HTTP API
+///
+/// Holds a single `reqwest::Client` together with the base URL, so it can be
+/// shared by Tauri commands, tests or background tasks.
+#[derive(Debug, Clone)]
+pub struct OllamaClient {
+    http: Client,
+    base_url: String,
+    stream_timeout: Duration,
+}
+
+/// Builder for `OllamaClient`
+pub struct OllamaClientBuilder {
+    base_url: String,
+    timeout: Duration,
+    stream_timeout: Duration,
+    headers: HeaderMap,
+    root_certificates: Vec<Certificate>,
+    accept_invalid_certs: bool,
+}
+
+impl OllamaClientBuilder {
+    /// Timeout for regular (non-streaming) requests
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Timeout for streamed chats and generations
+    pub fn stream_timeout(mut self, timeout: Duration) -> Self {
+        self.stream_timeout = timeout;
+        self
+    }
+
+    /// Add a header that is sent with every request
+    pub fn header(mut self, name: &str, value: &str) -> Result<Self, OllamaError> {
+        let name = HeaderName::from_bytes(name.as_bytes())
+            .map_err(|e| OllamaError::Other(format!("Invalid header name {}: {}", name, e)))?;
+        let value = HeaderValue::from_str(value)
+            .map_err(|e| OllamaError::Other(format!("Invalid header value for {}: {}", name, e)))?;
+        self.headers.insert(name, value);
+        Ok(self)
+    }
+
+    /// Send `Authorization: Bearer <token>` with every request
+    pub fn bearer_auth(self, token: &str) -> Result<Self, OllamaError> {
+        self.authorization(format!("Bearer {}", token.trim()))
+    }
+
+    /// Send HTTP basic credentials with every request
+    pub fn basic_auth(self, username: &str, password: &str) -> Result<Self, OllamaError> {
+        let credentials = BASE64.encode(format!("{}:{}", username, password));
+        self.authorization(format!("Basic {}", credentials))
+    }
+
+    // Marked sensitive so the value is redacted wherever reqwest prints headers
+    fn authorization(mut self, value: String) -> Result<Self, OllamaError> {
+        let mut value = HeaderValue::from_str(&value)
+            .map_err(|_| OllamaError::Other("Invalid characters in the credentials".to_string()))?;
+        value.set_sensitive(true);
+        self.headers.insert(AUTHORIZATION, value);
+        Ok(self)
+    }
+
+    /// Trust the certificates in a PEM file in addition to the system roots
+    pub fn root_certificate_file(mut self, path: &str) -> Result<Self, OllamaError> {
+        let pem = std::fs::read(path)
+            .map_err(|e| OllamaError::Other(format!("Failed to read certificate {}: {}", path, e)))?;
+        let certificates = Certificate::from_pem_bundle(&pem)
+            .map_err(|e| OllamaError::Other(format!("Invalid certificate {}: {}", path, e)))?;
+        self.root_certificates.extend(certificates);
+        Ok(self)
+    }
+
+    /// Accept any server certificate, for self-signed test servers only
+    pub fn accept_invalid_certs(mut self, accept: bool) -> Self {
+        self.accept_invalid_certs = accept;
+        self
+    }
+
+    pub fn build(self) -> Result<OllamaClient, OllamaError> {
+        let mut http = Client::builder()
+            .timeout(self.timeout)
+            .default_headers(self.headers)
+            .danger_accept_invalid_certs(self.accept_invalid_certs);
+        for certificate in self.root_certificates {
+            http = http.add_root_certificate(certificate);
+        }
+        let http = http
+            .build()
+            .map_err(|e| OllamaError::Other(format!("Failed to build HTTP client: {}", e)))?;
+
+        Ok(OllamaClient {
+            http,
+            base_url: self.base_url.trim_end_matches('/').to_string(),
+            stream_timeout: self.stream_timeout,
+        })
+    }
+}
+
+impl OllamaClient {
+    /// Start building a client for the given base URL (e.g. `http://127.0.0.1:11434`)
+    pub fn builder(base_url: impl Into<String>) -> OllamaClientBuilder {
+        OllamaClientBuilder {
+            base_url: base_url.into(),
+            timeout: DEFAULT_TIMEOUT,
+            stream_timeout: DEFAULT_STREAM_TIMEOUT,
+            headers: HeaderMap::new(),
+            root_certificates: Vec::new(),
+            accept_invalid_certs: false,
+        }
+    }
+
+    /// Create a client with default settings for the given base URL
+    pub fn new(base_url: impl Into<String>) -> Result<Self, OllamaError> {
+        Self::builder(base_url).build()
+    }
+
+    /// Create a client for the named server profile, or for the server resolved by the config manager
+    /// Priority: Profile argument > Active profile > User configuration > Environment variable > Default value
+    pub fn from_profile(profile: Option<&str>) -> Result<Self, OllamaError> {
+        let server = resolve_server(profile).map_err(OllamaError::Other)?;
+        Self::from_server(&server)
+    }
+
+    /// Create a client with the headers, credentials, TLS options and timeouts of a resolved server
+    pub fn from_server(server: &ServerConnection) -> Result<Self, OllamaError> {
+        let mut builder = Self::builder(server.host.clone()).accept_invalid_certs(server.tls.accept_invalid_certs);
+        // Credential headers come last so they win over profile headers of the same name
+        for (name, value) in server.headers.iter().chain(&server.auth.headers) {
+            builder = builder.header(name, value)?;
+        }
+        builder = match &server.auth.scheme {
+            Some(AuthScheme::Bearer { token }) => builder.bearer_auth(token)?,
+            Some(AuthScheme::Basic { username, password }) => builder.basic_auth(username, password)?,
+            None => builder,
+        };
+        if let Some(path) = server.tls.ca_cert_path.as_deref().filter(|path| !path.is_empty()) {
+            builder = builder.root_certificate_file(path)?;
+        }
+        if let Some(secs) = server.timeouts.request_secs.filter(|secs| *secs > 0) {
+            builder = builder.timeout(Duration::from_secs(secs));
+        }
+        if let Some(secs) = server.timeouts.stream_secs.filter(|secs| *secs > 0) {
+            builder = builder.stream_timeout(Duration::from_secs(secs));
+        }
+        builder.build()
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}{}", self.base_url, path)
+    }
+
+    /// Turn a non-success HTTP status into an error carrying Ollama's message
+    async fn check_status(response: Response) -> Result<Response, OllamaError> {
+        if response.status().is_success() {
+            Ok(response)
+        } else {
+            Err(OllamaError::from_response(response).await)
+        }
+    }
+
+    // ---- Server ----
+
+    /// Check whether the server answers on `/api/version`
+    ///
+    /// Rejected credentials are returned as `OllamaError::Unauthorized` rather
+    /// than `false`, so the caller can tell a wrong token from a down server.
+    pub async fn is_reachable(&self) -> Result<bool, OllamaError> {
+        match self.http.get(self.url("/api/version")).timeout(PING_TIMEOUT).send().await {
+            Ok(response) if response.status().is_success() => Ok(true),
+            Ok(response) => match OllamaError::from_response(response).await {
+                err @ OllamaError::Unauthorized { .. } => Err(err),
+                _ => Ok(false),
+            },
+            Err(_) => Ok(false),
+        }
+    }
+
+    /// GET /api/version
+    pub async fn version(&self) -> Result<OllamaVersion, OllamaError> {
+        let response = self.http.get(self.url("/api/version")).send().await?;
+        Self::check_status(response).await?.json::<OllamaVersion>().await
+            .map_err(OllamaError::from)
+    }
+
+    // ---- Models ----
+
+    /// GET /api/tags
+    pub async fn list_models(&self) -> Result<Vec<OllamaModel>, OllamaError> {
+        let response = self.http.get(self.url("/api/tags")).send().await?;
+        let model_list: OllamaModelList = Self::check_status(response).await?.json().await
+            .map_err(OllamaError::from)?;
+        Ok(model_list.models)
+    }
+
+    /// GET /api/ps
+    pub async fn list_running_models(&self) -> Result<Vec<OllamaRunningModel>, OllamaError> {
+        let response = self.http.get(self.url("/api/ps")).send().await?;
+        let model_list: OllamaRunningModelList = Self::check_status(response).await?.json().await
+            .map_err(OllamaError::from)?;
+        Ok(model_list.models)
+    }
+
+    /// POST /api/show
+    pub async fn show_model(&self, model: &str, verbose: bool) -> Result<ModelInfo, OllamaError> {
+        let payload = json!({
+            "model": model,
+            "verbose": verbose
+        });
+
+        let response = self.http.post(self.url("/api/show")).json(&payload).send().await?;
+        Self::check_status(response).await?.json::<ModelInfo>().await
+            .map_err(OllamaError::from)
+    }
+
+    /// POST /api/copy
+    pub async fn copy_model(&self, source: &str, destination: &str) -> Result<(), OllamaError> {
+        let payload = json!({
+            "source": source,
+            "destination": destination
+        });
+
+        let response = self.http.post(self.url("/api/copy")).json(&payload).send().await?;
+        Self::check_status(response).await?;
+        Ok(())
+    }
+
+    /// DELETE /api/delete, a missing model is `OllamaError::ModelNotFound`
+    pub async fn delete_model(&self, model: &str) -> Result<(), OllamaError> {
+        let payload = json!({
+            "model": model
+        });
+
+        let response = self.http.delete(self.url("/api/delete")).json(&payload).send().await?;
+        Self::check_status(response).await?;
+        Ok(())
+    }
+
+    /// POST /api/create (streaming), returns the raw response for line-by-line reading
+    pub async fn create_stream(&self, payload: &Value) -> Result<Response, OllamaError> {
+        let mut payload = payload.clone();
+        payload["stream"] = json!(true);
+
+        let response = self.http.post(self.url("/api/create"))
+            .json(&payload)
+            .timeout(OPERATION_TIMEOUT)
+            .send().await?;
+        Self::check_status(response).await
+    }
+
+    /// POST /api/pull (streaming), returns the raw response for line-by-line reading
+    ///
+    /// Large pulls run for hours, so stalls are caught by the caller's idle timeout instead
+    pub async fn pull_stream(&self, model: &str) -> Result<Response, OllamaError> {
+        let payload = json!({
+            "name": model,
+            "stream": true
+        });
+
+        self.http.post(self.url("/api/pull"))
+            .json(&payload)
+            .timeout(OPERATION_TIMEOUT)
+            .send().await
+            .map_err(OllamaError::from)
+    }
+
+    /// POST /api/push (streaming), returns the raw response for line-by-line reading
+    pub async fn push_stream(&self, model: &str, insecure: bool) -> Result<Response, OllamaError> {
+        let payload = json!({
+            "model": model,
+            "stream": true,
+            "insecure": insecure
+        });
+
+        let response = self.http.post(self.url("/api/push"))
+            .json(&payload)
+            .timeout(OPERATION_TIMEOUT)
+            .send().await?;
+        Self::check_status(response).await
+    }
+
+    /// HEAD /api/blobs/:digest
+    pub async fn blob_exists(&self, digest: &str) -> Result<bool, OllamaError> {
+        let response = self.http.head(self.url(&format!("/api/blobs/{}", digest))).send().await?;
+        Ok(response.status().is_success())
+    }
+
+    /// POST /api/blobs/:digest
+    ///
+    /// Multi-gigabyte weights take long on slow links, so the upload gets the operation timeout
+    pub async fn push_blob(&self, digest: &str, body: reqwest::Body) -> Result<(), OllamaError> {
+        let response = self.http.post(self.url(&format!("/api/blobs/{}", digest)))
+            .body(body)
+            .timeout(OPERATION_TIMEOUT)
+            .send().await?;
+        Self::check_status(response).await?;
+        Ok(())
+    }
+
+    // ---- Inference ----
+
+    /// POST /api/generate (non-streaming)
+    pub async fn generate(&self, request: &GenerateRequest) -> Result<GenerateResponse, OllamaError> {
+        let response = self.http.post(self.url("/api/generate")).json(request).send().await?;
+        let mut generate_response: GenerateResponse = Self::check_status(response).await?.json().await?;
+        generate_response.metrics = generate_response.metrics.with_throughput();
+        Ok(generate_response)
+    }
+
+    /// POST /api/generate (streaming), returns the raw response for line-by-line reading
+    pub async fn generate_stream(&self, request: &GenerateRequest) -> Result<Response, OllamaError> {
+        let response = self.http.post(self.url("/api/generate"))
+            .json(request)
+            .timeout(self.stream_timeout)
+            .send().await?;
+        Self::check_status(response).await
+    }
+
+    /// POST /api/chat (non-streaming)
+    pub async fn chat(&self, request: &ChatRequest) -> Result<ChatResponse, OllamaError> {
+        let response = self.http.post(self.url("/api/chat")).json(request).send().await?;
+        let mut chat_response: ChatResponse = Self::check_status(response).await?.json().await?;
+        chat_response.metrics = chat_response.metrics.with_throughput();
+        Ok(chat_response)
+    }
+
+    /// POST /api/chat (streaming), returns the raw response for line-by-line reading
+    pub async fn chat_stream(&self, request: &ChatRequest) -> Result<Response, OllamaError> {
+        let response = self.http.post(self.url("/api/chat"))
+            .json(request)
+            .timeout(self.stream_timeout)
+            .send().await?;
+        Self::check_status(response).await
+    }
+
+    /// POST /api/embed
+    pub async fn embed(&self, request: &EmbeddingsRequest) -> Result<EmbeddingsResponse, OllamaError> {
+        let response = self.http.post(self.url("/api/embed")).json(request).send().await?;
+        Self::check_status(response).await?.json::<EmbeddingsResponse>().await
+            .map_err(OllamaError::from)
+    }
+}
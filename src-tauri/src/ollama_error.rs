@@ -0,0 +1,236 @@
+use reqwest::{Response, StatusCode};
+use serde::ser::SerializeStruct;
+use serde::{Deserialize, Serialize, Serializer};
+use std::fmt;
+
+/// Error returned by the Ollama client and the `ollama_api` commands
+///
+/// Serialized to the frontend as `{ kind, message, status }` so the UI can
+/// branch on `kind` and still show `message` directly.
+#[derive(Debug, Clone)]
+pub enum OllamaError {
+    /// The server could not be reached (connection refused, DNS failure, ...)
+    ConnectionRefused(String),
+    /// The request or the response stream timed out
+    Timeout(String),
+    /// The connection broke off while sending the request or reading the response body
+    Interrupted(String),
+    /// Non-success HTTP status, with the message from Ollama's `{"error": "..."}` body
+    Http { status: u16, message: String },
+    /// Ollama answered 404 for the requested model
+    ModelNotFound(String),
+    /// The server or a proxy in front of it rejected the credentials (401 or 403)
+    Unauthorized { status: u16, message: String },
+    /// The response body could not be decoded
+    Parse(String),
+    /// The request was cancelled by the user
+    Cancelled,
+    /// Any other failure (configuration, client setup, ...)
+    Other(String),
+}
+
+// Shape of the body Ollama returns for failed requests
+#[derive(Debug, Deserialize)]
+struct OllamaErrorBody {
+    error: String,
+}
+
+impl OllamaError {
+    /// Stable identifier of the variant, used as `kind` on the frontend
+    pub fn kind(&self) -> &'static str {
+        match self {
+            OllamaError::ConnectionRefused(_) => "connection_refused",
+            OllamaError::Timeout(_) => "timeout",
+            OllamaError::Interrupted(_) => "interrupted",
+            OllamaError::Http { .. } => "http",
+            OllamaError::ModelNotFound(_) => "model_not_found",
+            OllamaError::Unauthorized { .. } => "unauthorized",
+            OllamaError::Parse(_) => "parse",
+            OllamaError::Cancelled => "cancelled",
+            OllamaError::Other(_) => "other",
+        }
+    }
+
+    /// HTTP status code, if the error came from an HTTP response
+    pub fn status(&self) -> Option<u16> {
+        match self {
+            OllamaError::Http { status, .. } | OllamaError::Unauthorized { status, .. } => Some(*status),
+            OllamaError::ModelNotFound(_) => Some(StatusCode::NOT_FOUND.as_u16()),
+            _ => None,
+        }
+    }
+
+    /// Whether retrying the same request may succeed (network failures, server errors)
+    pub fn is_transient(&self) -> bool {
+        match self {
+            OllamaError::ConnectionRefused(_) | OllamaError::Timeout(_) | OllamaError::Interrupted(_) => true,
+            OllamaError::Http { status, .. } => *status >= 500,
+            // Configuration and client setup errors fail the same way on every attempt
+            OllamaError::ModelNotFound(_)
+            | OllamaError::Unauthorized { .. }
+            | OllamaError::Parse(_)
+            | OllamaError::Cancelled
+            | OllamaError::Other(_) => false,
+        }
+    }
+
+    /// Build an error from a non-success response, keeping Ollama's error message
+    pub async fn from_response(response: Response) -> Self {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        Self::from_status_and_body(status, &body)
+    }
+
+    fn from_status_and_body(status: StatusCode, body: &str) -> Self {
+        let message = serde_json::from_str::<OllamaErrorBody>(body)
+            .map(|b| b.error)
+            .unwrap_or_else(|_| {
+                let body = body.trim();
+                if body.is_empty() {
+                    status.canonical_reason().unwrap_or("Unknown error").to_string()
+                } else {
+                    body.to_string()
+                }
+            });
+
+        if status == StatusCode::NOT_FOUND {
+            OllamaError::ModelNotFound(message)
+        } else if is_auth_failure(status) {
+            OllamaError::Unauthorized {
+                status: status.as_u16(),
+                message,
+            }
+        } else {
+            OllamaError::Http {
+                status: status.as_u16(),
+                message,
+            }
+        }
+    }
+}
+
+// Statuses an authenticating proxy answers with for missing or wrong credentials
+fn is_auth_failure(status: StatusCode) -> bool {
+    status == StatusCode::UNAUTHORIZED || status == StatusCode::FORBIDDEN
+}
+
+impl fmt::Display for OllamaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OllamaError::ConnectionRefused(msg) => write!(f, "Failed to connect to Ollama: {}", msg),
+            OllamaError::Timeout(msg) => write!(f, "Request to Ollama timed out: {}", msg),
+            OllamaError::Interrupted(msg) => write!(f, "Connection to Ollama interrupted: {}", msg),
+            OllamaError::Http { status, message } => write!(f, "HTTP error {}: {}", status, message),
+            OllamaError::ModelNotFound(msg) => write!(f, "{}", msg),
+            OllamaError::Unauthorized { status, message } => {
+                write!(f, "Authentication failed (HTTP {}): {}", status, message)
+            }
+            OllamaError::Parse(msg) => write!(f, "Failed to parse response: {}", msg),
+            OllamaError::Cancelled => write!(f, "Request cancelled by user"),
+            OllamaError::Other(msg) => write!(f, "{}", msg),
+        }
+    }
+}
+
+impl std::error::Error for OllamaError {}
+
+impl Serialize for OllamaError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("OllamaError", 3)?;
+        state.serialize_field("kind", self.kind())?;
+        state.serialize_field("message", &self.to_string())?;
+        state.serialize_field("status", &self.status())?;
+        state.end()
+    }
+}
+
+impl From<reqwest::Error> for OllamaError {
+    fn from(e: reqwest::Error) -> Self {
+        if e.is_timeout() {
+            OllamaError::Timeout(e.to_string())
+        } else if e.is_connect() {
+            OllamaError::ConnectionRefused(e.to_string())
+        } else if e.is_decode() {
+            OllamaError::Parse(e.to_string())
+        } else if e.is_body() || e.is_request() {
+            OllamaError::Interrupted(e.to_string())
+        } else if let Some(status) = e.status().filter(|status| is_auth_failure(*status)) {
+            OllamaError::Unauthorized {
+                status: status.as_u16(),
+                message: e.to_string(),
+            }
+        } else if let Some(status) = e.status() {
+            OllamaError::Http {
+                status: status.as_u16(),
+                message: e.to_string(),
+            }
+        } else {
+            OllamaError::Other(e.to_string())
+        }
+    }
+}
+
+impl From<serde_json::Error> for OllamaError {
+    fn from(e: serde_json::Error) -> Self {
+        OllamaError::Parse(e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_body_is_kept() {
+        let err = OllamaError::from_status_and_body(
+            StatusCode::BAD_REQUEST,
+            r#"{"error":"invalid model name"}"#,
+        );
+        assert_eq!(err.kind(), "http");
+        assert_eq!(err.status(), Some(400));
+        assert_eq!(err.to_string(), "HTTP error 400: invalid model name");
+    }
+
+    #[test]
+    fn test_not_found_maps_to_model_not_found() {
+        let err = OllamaError::from_status_and_body(
+            StatusCode::NOT_FOUND,
+            r#"{"error":"model 'llama9' not found"}"#,
+        );
+        assert_eq!(err.kind(), "model_not_found");
+
+        let json = serde_json::to_value(&err).unwrap();
+        assert_eq!(json["kind"], "model_not_found");
+        assert_eq!(json["status"], 404);
+        assert_eq!(json["message"], "model 'llama9' not found");
+    }
+
+    #[test]
+    fn test_auth_failures_map_to_unauthorized() {
+        let err = OllamaError::from_status_and_body(StatusCode::UNAUTHORIZED, "401 Authorization Required");
+        assert_eq!(err.kind(), "unauthorized");
+        assert_eq!(err.status(), Some(401));
+        assert_eq!(err.to_string(), "Authentication failed (HTTP 401): 401 Authorization Required");
+        assert!(!err.is_transient());
+
+        let err = OllamaError::from_status_and_body(StatusCode::FORBIDDEN, "");
+        assert_eq!(serde_json::to_value(&err).unwrap()["kind"], "unauthorized");
+    }
+
+    #[test]
+    fn test_transient_errors() {
+        assert!(OllamaError::Timeout("read timed out".to_string()).is_transient());
+        assert!(OllamaError::from_status_and_body(StatusCode::BAD_GATEWAY, "").is_transient());
+        assert!(!OllamaError::from_status_and_body(StatusCode::NOT_FOUND, "").is_transient());
+        assert!(!OllamaError::from_status_and_body(StatusCode::BAD_REQUEST, "").is_transient());
+        assert!(!OllamaError::Cancelled.is_transient());
+        assert!(OllamaError::Interrupted("connection reset".to_string()).is_transient());
+        assert!(!OllamaError::Other("Unknown server profile: gpu".to_string()).is_transient());
+    }
+
+    #[test]
+    fn test_non_json_body_falls_back_to_text() {
+        let err = OllamaError::from_status_and_body(StatusCode::BAD_GATEWAY, "");
+        assert_eq!(err.to_string(), "HTTP error 502: Bad Gateway");
+    }
+}
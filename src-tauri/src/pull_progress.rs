@@ -0,0 +1,176 @@
+use serde::Serialize;
+use std::time::{Duration, Instant};
+
+// Minimum time between two speed samples, shorter intervals are too noisy
+const SPEED_SAMPLE_INTERVAL: Duration = Duration::from_millis(500);
+// Weight of the newest sample in the exponential moving average of the speed
+const SPEED_SMOOTHING: f64 = 0.3;
+
+/// Aggregated progress over all layers of a pull
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct PullProgressSummary {
+    pub overall_completed: i64,
+    pub overall_total: i64,
+    pub overall_percent: f64,
+    /// Digest of the layer that reported progress last
+    pub current_layer: Option<String>,
+    /// 1-based index of the current layer
+    pub current_layer_index: Option<usize>,
+    pub layer_count: usize,
+    /// Smoothed download speed in bytes per second
+    pub bytes_per_second: f64,
+    pub eta_seconds: Option<f64>,
+}
+
+#[derive(Debug, Clone)]
+struct LayerProgress {
+    digest: String,
+    total: i64,
+    completed: i64,
+}
+
+/// Tracks `/api/pull` progress per layer digest
+///
+/// Ollama reports `total`/`completed` for one layer at a time, so a single
+/// counter jumps backwards whenever the next layer starts. The tracker keeps
+/// every layer and sums them up instead.
+#[derive(Debug, Default)]
+pub struct PullProgressTracker {
+    layers: Vec<LayerProgress>,
+    current: Option<usize>,
+    // Bytes counted at the last speed sample
+    sampled_bytes: i64,
+    sampled_at: Option<Instant>,
+    bytes_per_second: f64,
+}
+
+impl PullProgressTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a progress line for `digest`
+    pub fn update(&mut self, digest: &str, total: Option<i64>, completed: Option<i64>, now: Instant) {
+        let index = match self.layers.iter().position(|l| l.digest == digest) {
+            Some(index) => index,
+            None => {
+                let completed = completed.unwrap_or(0);
+                // Bytes a new layer already has (e.g. after a resume) were not downloaded now
+                self.sampled_bytes += completed;
+                self.layers.push(LayerProgress {
+                    digest: digest.to_string(),
+                    total: total.unwrap_or(0),
+                    completed,
+                });
+                self.layers.len() - 1
+            }
+        };
+
+        let layer = &mut self.layers[index];
+        if let Some(total) = total {
+            layer.total = total;
+        }
+        if let Some(completed) = completed {
+            layer.completed = completed.max(layer.completed);
+        }
+        self.current = Some(index);
+
+        self.sample_speed(now);
+    }
+
+    fn sample_speed(&mut self, now: Instant) {
+        let completed = self.completed_bytes();
+        let Some(sampled_at) = self.sampled_at else {
+            self.sampled_at = Some(now);
+            self.sampled_bytes = completed;
+            return;
+        };
+
+        let elapsed = now.duration_since(sampled_at);
+        if elapsed < SPEED_SAMPLE_INTERVAL {
+            return;
+        }
+
+        let rate = (completed - self.sampled_bytes).max(0) as f64 / elapsed.as_secs_f64();
+        self.bytes_per_second = if self.bytes_per_second == 0.0 {
+            rate
+        } else {
+            SPEED_SMOOTHING * rate + (1.0 - SPEED_SMOOTHING) * self.bytes_per_second
+        };
+        self.sampled_at = Some(now);
+        self.sampled_bytes = completed;
+    }
+
+    pub fn completed_bytes(&self) -> i64 {
+        self.layers.iter().map(|l| l.completed).sum()
+    }
+
+    pub fn total_bytes(&self) -> i64 {
+        self.layers.iter().map(|l| l.total).sum()
+    }
+
+    pub fn summary(&self) -> PullProgressSummary {
+        let overall_completed = self.completed_bytes();
+        let overall_total = self.total_bytes();
+        let overall_percent = if overall_total > 0 {
+            (overall_completed as f64 / overall_total as f64 * 100.0).min(100.0)
+        } else {
+            0.0
+        };
+        let eta_seconds = if self.bytes_per_second > 0.0 {
+            Some((overall_total - overall_completed).max(0) as f64 / self.bytes_per_second)
+        } else {
+            None
+        };
+
+        PullProgressSummary {
+            overall_completed,
+            overall_total,
+            overall_percent,
+            current_layer: self.current.map(|i| self.layers[i].digest.clone()),
+            current_layer_index: self.current.map(|i| i + 1),
+            layer_count: self.layers.len(),
+            bytes_per_second: self.bytes_per_second,
+            eta_seconds,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_layers_are_summed_instead_of_reset() {
+        let start = Instant::now();
+        let mut tracker = PullProgressTracker::new();
+        tracker.update("sha256:a", Some(1000), Some(1000), start);
+        tracker.update("sha256:b", Some(3000), Some(0), start);
+        tracker.update("sha256:b", Some(3000), Some(1000), start + Duration::from_secs(1));
+
+        let summary = tracker.summary();
+        assert_eq!(summary.overall_completed, 2000);
+        assert_eq!(summary.overall_total, 4000);
+        assert_eq!(summary.overall_percent, 50.0);
+        assert_eq!(summary.current_layer.as_deref(), Some("sha256:b"));
+        assert_eq!(summary.current_layer_index, Some(2));
+        assert_eq!(summary.layer_count, 2);
+    }
+
+    #[test]
+    fn test_speed_is_smoothed_and_ignores_existing_bytes() {
+        let start = Instant::now();
+        let mut tracker = PullProgressTracker::new();
+        tracker.update("sha256:a", Some(10_000), Some(0), start);
+        tracker.update("sha256:a", Some(10_000), Some(1000), start + Duration::from_secs(1));
+        assert_eq!(tracker.summary().bytes_per_second, 1000.0);
+
+        // A layer that is already on disk does not count as downloaded bytes
+        tracker.update("sha256:b", Some(50_000), Some(50_000), start + Duration::from_millis(1200));
+        tracker.update("sha256:a", Some(10_000), Some(3000), start + Duration::from_secs(3));
+        let summary = tracker.summary();
+        // 2000 bytes in 2s after 1000 B/s: 0.3 * 1000 + 0.7 * 1000
+        assert!((summary.bytes_per_second - 1000.0).abs() < 1e-6);
+        assert_eq!(summary.eta_seconds, Some(7.0));
+    }
+}
@@ -0,0 +1,73 @@
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::time::Duration;
+
+/// Retry policy for interrupted model pulls
+///
+/// Each retry reissues `/api/pull`; Ollama keeps the blobs it already has, so
+/// a retry resumes instead of starting over.
+#[derive(Debug, Clone)]
+pub struct PullRetryPolicy {
+    /// Total number of attempts, including the first one
+    pub max_attempts: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl Default for PullRetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 5,
+            base_delay: Duration::from_secs(2),
+            max_delay: Duration::from_secs(60),
+        }
+    }
+}
+
+impl PullRetryPolicy {
+    /// Backoff before the retry that follows `failed_attempt` (1-based)
+    pub fn delay(&self, failed_attempt: u32) -> Duration {
+        self.delay_with_jitter(failed_attempt, random_fraction())
+    }
+
+    /// Exponential backoff capped at `max_delay`, with "equal jitter": half of
+    /// the delay is fixed and `jitter` (0.0..1.0) scales the other half, so
+    /// clients that failed together don't retry together.
+    pub fn delay_with_jitter(&self, failed_attempt: u32, jitter: f64) -> Duration {
+        let exponent = failed_attempt.saturating_sub(1).min(16);
+        let backoff = self.base_delay
+            .saturating_mul(1 << exponent)
+            .min(self.max_delay);
+        let half = backoff / 2;
+        half + half.mul_f64(jitter.clamp(0.0, 1.0))
+    }
+}
+
+// Random value in 0.0..1.0 from the randomly seeded std hasher, avoids pulling in a rand dependency
+fn random_fraction() -> f64 {
+    let value = RandomState::new().build_hasher().finish();
+    (value >> 11) as f64 / (1u64 << 53) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff_doubles_and_is_capped() {
+        let policy = PullRetryPolicy::default();
+        assert_eq!(policy.delay_with_jitter(1, 1.0), Duration::from_secs(2));
+        assert_eq!(policy.delay_with_jitter(2, 1.0), Duration::from_secs(4));
+        assert_eq!(policy.delay_with_jitter(3, 0.0), Duration::from_secs(4));
+        assert_eq!(policy.delay_with_jitter(10, 1.0), Duration::from_secs(60));
+    }
+
+    #[test]
+    fn test_jitter_stays_within_bounds() {
+        let policy = PullRetryPolicy::default();
+        for _ in 0..100 {
+            let delay = policy.delay(3);
+            assert!(delay >= Duration::from_secs(4) && delay <= Duration::from_secs(8), "{:?}", delay);
+        }
+    }
+}
@@ -432,4 +432,15 @@ pub fn get_system_info_for_path(storage_path: &str) -> Result<SystemInfo> {
     let mut monitor = SystemMonitor::new();
     monitor.refresh();
     Ok(monitor.get_system_info_for_path(storage_path))
+}
+
+/// Free bytes on the disk that holds `path`, `None` if no disk matches
+pub fn get_available_space_for_path(path: &str) -> Option<u64> {
+    let system = System::new_with_specifics(RefreshKind::new().with_disks_list());
+    let target_path = Path::new(path);
+    system.disks()
+        .iter()
+        .filter(|disk| target_path.starts_with(disk.mount_point()))
+        .max_by_key(|disk| disk.mount_point().as_os_str().len())
+        .map(|disk| disk.available_space())
 }
\ No newline at end of file
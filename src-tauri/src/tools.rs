@@ -0,0 +1,258 @@
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use crate::ollama_client::OllamaClient;
+use crate::system_monitor::get_system_info;
+
+// Maximum number of bytes returned by the read_file tool
+const MAX_READ_BYTES: usize = 256 * 1024;
+
+/// Built-in tools the backend can execute on behalf of a model
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BuiltinTool {
+    ReadFile,
+    GetSystemInfo,
+    ListModels,
+}
+
+impl BuiltinTool {
+    pub fn name(&self) -> &'static str {
+        match self {
+            BuiltinTool::ReadFile => "read_file",
+            BuiltinTool::GetSystemInfo => "get_system_info",
+            BuiltinTool::ListModels => "list_models",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "read_file" => Some(BuiltinTool::ReadFile),
+            "get_system_info" => Some(BuiltinTool::GetSystemInfo),
+            "list_models" => Some(BuiltinTool::ListModels),
+            _ => None,
+        }
+    }
+
+    /// Tool definition in the format Ollama expects in `ChatRequest.tools`
+    pub fn definition(&self) -> Value {
+        match self {
+            BuiltinTool::ReadFile => json!({
+                "type": "function",
+                "function": {
+                    "name": self.name(),
+                    "description": "Read a UTF-8 text file from one of the directories the user allowed",
+                    "parameters": {
+                        "type": "object",
+                        "properties": {
+                            "path": {
+                                "type": "string",
+                                "description": "Absolute path of the file to read"
+                            }
+                        },
+                        "required": ["path"]
+                    }
+                }
+            }),
+            BuiltinTool::GetSystemInfo => json!({
+                "type": "function",
+                "function": {
+                    "name": self.name(),
+                    "description": "Get CPU, memory, disk and GPU information of the machine running Ollama",
+                    "parameters": {
+                        "type": "object",
+                        "properties": {}
+                    }
+                }
+            }),
+            BuiltinTool::ListModels => json!({
+                "type": "function",
+                "function": {
+                    "name": self.name(),
+                    "description": "List the models installed on the Ollama server",
+                    "parameters": {
+                        "type": "object",
+                        "properties": {}
+                    }
+                }
+            }),
+        }
+    }
+}
+
+/// A tool call requested by the model
+#[derive(Debug, Clone, Serialize)]
+pub struct ToolCall {
+    pub name: String,
+    pub arguments: Value,
+}
+
+impl ToolCall {
+    /// Parse an entry of `ChatMessage.tool_calls` (`{"function": {"name", "arguments"}}`)
+    pub fn from_value(value: &Value) -> Option<Self> {
+        let function = value.get("function")?;
+        let name = function.get("name")?.as_str()?.to_string();
+        let arguments = match function.get("arguments") {
+            // Some models send the arguments as a JSON encoded string
+            Some(Value::String(raw)) => serde_json::from_str(raw).unwrap_or(Value::Null),
+            Some(arguments) => arguments.clone(),
+            None => Value::Null,
+        };
+        Some(Self { name, arguments })
+    }
+}
+
+/// Registry of the tools offered to the model in a tool-calling chat
+pub struct ToolRegistry {
+    tools: Vec<BuiltinTool>,
+    allowed_dirs: Vec<PathBuf>,
+    client: OllamaClient,
+}
+
+impl ToolRegistry {
+    /// read_file is only registered when at least one allowed directory exists
+    pub fn new(client: OllamaClient, allowed_dirs: &[String]) -> Self {
+        let allowed_dirs: Vec<PathBuf> = allowed_dirs
+            .iter()
+            .filter_map(|dir| fs::canonicalize(dir).ok())
+            .collect();
+
+        let mut tools = vec![BuiltinTool::GetSystemInfo, BuiltinTool::ListModels];
+        if !allowed_dirs.is_empty() {
+            tools.insert(0, BuiltinTool::ReadFile);
+        }
+
+        Self {
+            tools,
+            allowed_dirs,
+            client,
+        }
+    }
+
+    /// Definitions to send as `ChatRequest.tools`
+    pub fn definitions(&self) -> Vec<Value> {
+        self.tools.iter().map(|tool| tool.definition()).collect()
+    }
+
+    /// Run a tool call, the result (or error) is returned as text for the tool message
+    pub async fn execute(&self, call: &ToolCall) -> Result<String, String> {
+        let tool = BuiltinTool::from_name(&call.name)
+            .filter(|tool| self.tools.contains(tool))
+            .ok_or_else(|| format!("Unknown tool: {}", call.name))?;
+
+        match tool {
+            BuiltinTool::ReadFile => {
+                let path = call.arguments.get("path")
+                    .and_then(|p| p.as_str())
+                    .ok_or_else(|| "Missing required argument: path".to_string())?
+                    .to_string();
+                // File IO blocks, keep it off the async runtime
+                let allowed_dirs = self.allowed_dirs.clone();
+                tokio::task::spawn_blocking(move || read_allowed_file(&allowed_dirs, &path))
+                    .await
+                    .map_err(|e| format!("Failed to read file: {}", e))?
+            }
+            BuiltinTool::GetSystemInfo => {
+                // System info sampling sleeps briefly, keep it off the async runtime
+                let info = tokio::task::spawn_blocking(get_system_info)
+                    .await
+                    .map_err(|e| format!("Failed to get system info: {}", e))?
+                    .map_err(|e| format!("Failed to get system info: {}", e))?;
+                serde_json::to_string(&info).map_err(|e| e.to_string())
+            }
+            BuiltinTool::ListModels => {
+                let models = self.client.list_models().await.map_err(|e| e.to_string())?;
+                let summary: Vec<Value> = models
+                    .iter()
+                    .map(|m| json!({
+                        "name": m.name,
+                        "size": m.size,
+                        "parameter_size": m.details.parameter_size,
+                        "quantization_level": m.details.quantization_level,
+                    }))
+                    .collect();
+                serde_json::to_string(&summary).map_err(|e| e.to_string())
+            }
+        }
+    }
+}
+
+/// Check that a path resolves inside one of the allowed directories
+fn resolve_allowed_path(allowed_dirs: &[PathBuf], path: &str) -> Result<PathBuf, String> {
+    // Canonicalize to resolve `..` and symlinks before the containment check
+    let resolved = fs::canonicalize(Path::new(path))
+        .map_err(|e| format!("Failed to access {}: {}", path, e))?;
+
+    if allowed_dirs.iter().any(|dir| resolved.starts_with(dir)) {
+        Ok(resolved)
+    } else {
+        Err(format!("Access denied: {} is outside the allowed directories", path))
+    }
+}
+
+// Reads at most MAX_READ_BYTES + 1 bytes, so a large model file in an allowed dir is never loaded whole
+fn read_allowed_file(allowed_dirs: &[PathBuf], path: &str) -> Result<String, String> {
+    let resolved = resolve_allowed_path(allowed_dirs, path)?;
+    let file = fs::File::open(&resolved)
+        .map_err(|e| format!("Failed to read {}: {}", path, e))?;
+    let file_size = file.metadata().map(|m| m.len()).unwrap_or_default();
+
+    let mut bytes = Vec::new();
+    file.take(MAX_READ_BYTES as u64 + 1)
+        .read_to_end(&mut bytes)
+        .map_err(|e| format!("Failed to read {}: {}", path, e))?;
+
+    if bytes.len() > MAX_READ_BYTES {
+        let content = String::from_utf8_lossy(&bytes[..MAX_READ_BYTES]);
+        Ok(format!("{}\n[truncated: file is {} bytes]", content, file_size))
+    } else {
+        Ok(String::from_utf8_lossy(&bytes).to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_file_stays_inside_allowed_dirs() {
+        let base = std::env::temp_dir().join(format!("ollama-pro-tools-{}", std::process::id()));
+        let allowed = base.join("allowed");
+        fs::create_dir_all(&allowed).unwrap();
+        fs::write(allowed.join("notes.txt"), "hello").unwrap();
+        fs::write(base.join("secret.txt"), "secret").unwrap();
+
+        let allowed_dirs = vec![fs::canonicalize(&allowed).unwrap()];
+        let inside = allowed.join("notes.txt");
+        assert_eq!(read_allowed_file(&allowed_dirs, inside.to_str().unwrap()).unwrap(), "hello");
+
+        // Large files are cut at MAX_READ_BYTES
+        let large = allowed.join("large.txt");
+        fs::write(&large, "x".repeat(MAX_READ_BYTES + 10)).unwrap();
+        let content = read_allowed_file(&allowed_dirs, large.to_str().unwrap()).unwrap();
+        assert!(content.ends_with(&format!("\n[truncated: file is {} bytes]", MAX_READ_BYTES + 10)));
+        assert_eq!(content.matches('x').count(), MAX_READ_BYTES);
+
+        // `..` must not escape the allowed directory
+        let escape = allowed.join("..").join("secret.txt");
+        assert!(read_allowed_file(&allowed_dirs, escape.to_str().unwrap()).is_err());
+
+        fs::remove_dir_all(&base).unwrap();
+    }
+
+    #[test]
+    fn test_tool_call_parsing() {
+        let call = ToolCall::from_value(&json!({
+            "function": { "name": "read_file", "arguments": { "path": "/tmp/a.txt" } }
+        })).unwrap();
+        assert_eq!(call.name, "read_file");
+        assert_eq!(call.arguments["path"], "/tmp/a.txt");
+
+        // String-encoded arguments are decoded
+        let call = ToolCall::from_value(&json!({
+            "function": { "name": "list_models", "arguments": "{}" }
+        })).unwrap();
+        assert_eq!(call.arguments, json!({}));
+    }
+}
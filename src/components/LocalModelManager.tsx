@@ -100,15 +100,13 @@ const LocalModelManager = () => {
 
   const handleDeleteModel = async (modelName: string) => {
     try {
-      const success = await ollamaTauriApi.deleteModel(modelName);
-      if (success) {
-        toast({
-          title: t('localModels.deleteSuccess'),
-          description: t('localModels.deleteSuccessMessage', { modelName }),
-          className: 'bg-black/80 backdrop-blur-sm border-white/20 text-white',
-        });
-        fetchLocalModels();
-      }
+      await ollamaTauriApi.deleteModel(modelName);
+      toast({
+        title: t('localModels.deleteSuccess'),
+        description: t('localModels.deleteSuccessMessage', { modelName }),
+        className: 'bg-black/80 backdrop-blur-sm border-white/20 text-white',
+      });
+      fetchLocalModels();
     } catch (error) {
       // Failed to delete model
       toast({
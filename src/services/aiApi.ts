@@ -4,6 +4,7 @@ import { AIConfig, MODEL_PROVIDERS, RemoteModelProvider } from '@/types/ai';
 
 import configApi from './configApi';
 import { modelService } from './modelService';
+import { ChatStreamChunk, formatBackendError } from './ollamaTauriApi';
 
 export interface ChatMessage {
   id: string;
@@ -112,7 +113,7 @@ class AIApiService {
       
       if (useStream) {
         // Listen for streaming events from Rust backend
-        unlisten = await listen<{ content: string; done: boolean }>('ollama-chat-stream', (event) => {
+        unlisten = await listen<ChatStreamChunk>('ollama-chat-stream', (event) => {
           const { content, done } = event.payload;
           
           // Call callback function to handle each chunk
@@ -159,7 +160,7 @@ class AIApiService {
       }
     } catch (error) {
       console.error('Failed to generate chat completion:', error);
-      throw new Error(`Chat completion failed: ${error}`);
+      throw new Error(`Chat completion failed: ${formatBackendError(error)}`);
     }
   }
 
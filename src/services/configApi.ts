@@ -1,4 +1,5 @@
 import { invoke } from '@tauri-apps/api/tauri';
+import { isOllamaBackendError } from './ollamaTauriApi';
 
 export interface ConfigInfo {
   config_path: string;
@@ -8,12 +9,106 @@ export interface ConfigInfo {
   user_configured_models_path: string | null;
   env_models_path: string | null;
   effective_models_path: string;
+  active_profile: string | null;
+}
+
+export interface TlsOptions {
+  // PEM file with an extra root certificate
+  ca_cert_path?: string | null;
+  // Skip certificate validation, only for self-signed test servers
+  accept_invalid_certs?: boolean;
+}
+
+export interface TimeoutOptions {
+  // Seconds for regular requests
+  request_secs?: number | null;
+  // Seconds for streamed chats and generations
+  stream_secs?: number | null;
+}
+
+/**
+ * A named Ollama server
+ */
+export interface ServerProfile {
+  host: string;
+  // Credential headers (Authorization, *-Key, *-Token) are moved to the host's keychain entry
+  headers?: Record<string, string>;
+  tls?: TlsOptions;
+  timeouts?: TimeoutOptions;
+  models_path?: string | null;
+}
+
+/**
+ * A server profile as returned by the backend, header values are left out
+ */
+export interface ServerProfileSummary {
+  host: string;
+  header_names: string[];
+  tls: TlsOptions;
+  timeouts: TimeoutOptions;
+  models_path: string | null;
+}
+
+export interface ServerProfiles {
+  active_profile: string | null;
+  profiles: Record<string, ServerProfileSummary>;
+}
+
+export type AuthScheme =
+  | { type: 'bearer'; token: string }
+  | { type: 'basic'; username: string; password: string };
+
+/**
+ * Credentials for a host behind an authenticating reverse proxy,
+ * stored in the OS keychain and never returned by the backend
+ */
+export interface HostAuth {
+  scheme?: AuthScheme | null;
+  // Custom headers such as an API key header
+  headers?: Record<string, string>;
+}
+
+/**
+ * Stored credentials of a host as returned by the backend, without secrets
+ */
+export interface HostAuthSummary {
+  host: string;
+  scheme: 'bearer' | 'basic' | null;
+  username: string | null;
+  header_names: string[];
+}
+
+export interface DownloadWindow {
+  start: string;
+  end: string;
+}
+
+export type MigrationPhase = 'planning' | 'copying' | 'cleanup' | 'done' | 'failed';
+
+export interface MigrationProgress {
+  phase: MigrationPhase;
+  files_done: number;
+  files_total: number;
+  bytes_done: number;
+  bytes_total: number;
+  current_file: string | null;
+  error: string | null;
+}
+
+export interface MigrationReport {
+  source: string;
+  destination: string;
+  files_copied: number;
+  // Files already complete at the destination from an earlier, interrupted run
+  files_skipped: number;
+  bytes_copied: number;
+  files_removed: number;
 }
 
 class ConfigApi {
   /**
    * Get the current effective Ollama host address
-   * Priority: User configuration > Environment variables > Default value
+   * Priority: Active profile > User configuration > Environment variables > Default value
    */
   async getOllamaHost(): Promise<string> {
     try {
@@ -78,6 +173,10 @@ class ConfigApi {
       // and centralizes the host validation logic in Rust
       return await invoke<boolean>('validate_host', { host });
     } catch (error) {
+      // The host answered but rejected the stored credentials
+      if (isOllamaBackendError(error) && error.kind === 'unauthorized') {
+        throw error;
+      }
       // Host validation failed
       console.error('Host validation failed:', error);
       return false;
@@ -144,7 +243,7 @@ class ConfigApi {
 
   /**
    * Get Ollama models storage path
-   * Priority: User configuration > Environment variables > Default value
+   * Priority: Active profile > User configuration > Environment variables > Default value
    */
   async getOllamaModelsPath(): Promise<string> {
     try {
@@ -175,6 +274,31 @@ class ConfigApi {
     }
   }
 
+  /**
+   * Copy the existing models to a new models path, then switch the configuration to it
+   * Running it again after an interruption continues the copy
+   * @param newPath New storage path for Ollama models
+   * @param cleanup Remove the models from the old location afterwards
+   */
+  async migrateModelsDir(newPath: string, cleanup: boolean): Promise<MigrationReport> {
+    try {
+      return await invoke<MigrationReport>('migrate_models_dir', { newPath, cleanup });
+    } catch (error) {
+      throw new Error(`Failed to migrate models directory: ${error}`);
+    }
+  }
+
+  /**
+   * Listen for models directory migration progress
+   * @returns Function that removes the listener
+   */
+  async onMigrationProgress(callback: (progress: MigrationProgress) => void): Promise<() => void> {
+    const { listen } = await import('@tauri-apps/api/event');
+    return listen<MigrationProgress>('models-migration-progress', (event) => {
+      callback(event.payload);
+    });
+  }
+
   /**
    * Clear user-configured models path
    * After clearing, will fallback to environment variables or default value
@@ -189,6 +313,141 @@ class ConfigApi {
     }
   }
 
+  /**
+   * Get the maximum number of model downloads that run at the same time
+   */
+  async getMaxConcurrentDownloads(): Promise<number> {
+    try {
+      return await invoke<number>('get_max_concurrent_downloads');
+    } catch (error) {
+      // Failed to get download limit, use the backend default
+      return 2;
+    }
+  }
+
+  /**
+   * Set the maximum number of concurrent downloads
+   * @param max New limit, 0 restores the default
+   * @returns Effective limit after setting
+   */
+  async setMaxConcurrentDownloads(max: number): Promise<number> {
+    try {
+      return await invoke<number>('set_max_concurrent_downloads', { max });
+    } catch (error) {
+      throw new Error(`Failed to set max concurrent downloads: ${error}`);
+    }
+  }
+
+  /**
+   * Get the daily window (HH:MM local time) in which downloads may run
+   * @returns The window, or null if downloads may run at any time
+   */
+  async getDownloadWindow(): Promise<DownloadWindow | null> {
+    try {
+      return await invoke<DownloadWindow | null>('get_download_window');
+    } catch (error) {
+      // Failed to get download window, downloads are not restricted
+      return null;
+    }
+  }
+
+  /**
+   * Set the download window, e.g. 22:00 - 07:00
+   * @param start Window start (HH:MM), omit to clear the window
+   * @param end Window end (HH:MM), omit to clear the window
+   * @returns Effective window after setting
+   */
+  async setDownloadWindow(start?: string, end?: string): Promise<DownloadWindow | null> {
+    try {
+      return await invoke<DownloadWindow | null>('set_download_window', { start, end });
+    } catch (error) {
+      throw new Error(`Failed to set download window: ${error}`);
+    }
+  }
+
+  /**
+   * Get the server profiles and the active one
+   */
+  async getServerProfiles(): Promise<ServerProfiles> {
+    try {
+      return await invoke<ServerProfiles>('get_server_profiles');
+    } catch (error) {
+      throw new Error(`Failed to get server profiles: ${error}`);
+    }
+  }
+
+  /**
+   * Add or replace a server profile
+   * @param name Profile name, e.g. "gpu-box"
+   * @param profile Host, headers, TLS options and models path
+   */
+  async saveServerProfile(name: string, profile: ServerProfile): Promise<ServerProfiles> {
+    try {
+      return await invoke<ServerProfiles>('save_server_profile', { name, profile });
+    } catch (error) {
+      throw new Error(`Failed to save server profile: ${error}`);
+    }
+  }
+
+  /**
+   * Delete a server profile, deleting the active one goes back to the host settings
+   */
+  async deleteServerProfile(name: string): Promise<ServerProfiles> {
+    try {
+      return await invoke<ServerProfiles>('delete_server_profile', { name });
+    } catch (error) {
+      throw new Error(`Failed to delete server profile: ${error}`);
+    }
+  }
+
+  /**
+   * Switch the active server profile
+   * @param name Profile name, omit to use the host settings
+   * (user configuration > OLLAMA_HOST > default) again
+   */
+  async setActiveProfile(name?: string): Promise<ServerProfiles> {
+    try {
+      return await invoke<ServerProfiles>('set_active_profile', { name });
+    } catch (error) {
+      throw new Error(`Failed to set active profile: ${error}`);
+    }
+  }
+
+  /**
+   * List the hosts with stored credentials, secrets are never returned
+   */
+  async getHostAuth(): Promise<HostAuthSummary[]> {
+    try {
+      return await invoke<HostAuthSummary[]>('get_host_auth');
+    } catch (error) {
+      throw new Error(`Failed to get host credentials: ${error}`);
+    }
+  }
+
+  /**
+   * Store credentials for a host, used by every request to it
+   * @param host Host address, normalized like the host setting
+   * @param auth Bearer or basic credentials and custom headers, empty removes them
+   */
+  async setHostAuth(host: string, auth: HostAuth): Promise<HostAuthSummary[]> {
+    try {
+      return await invoke<HostAuthSummary[]>('set_host_auth', { host, auth });
+    } catch (error) {
+      throw new Error(`Failed to set host credentials: ${error}`);
+    }
+  }
+
+  /**
+   * Remove the stored credentials for a host
+   */
+  async clearHostAuth(host: string): Promise<HostAuthSummary[]> {
+    try {
+      return await invoke<HostAuthSummary[]>('clear_host_auth', { host });
+    } catch (error) {
+      throw new Error(`Failed to clear host credentials: ${error}`);
+    }
+  }
+
   /**
    * Validate if the models path format is valid
    * @param path Models path to validate
@@ -9,10 +9,33 @@ import {
 export interface ChatMessage {
   role: 'system' | 'user' | 'assistant' | 'tool';
   content: string;
+  thinking?: string;
   images?: string[];
   tool_calls?: any[];
+  tool_name?: string;
 }
 
+/**
+ * Step event emitted on the caller's channel by chat_with_tools
+ */
+export type ToolChatEvent =
+  | { type: 'assistant'; step: number; message: ChatMessage }
+  | {
+      type: 'tool_result';
+      step: number;
+      name: string;
+      arguments: any;
+      content: string;
+      is_error: boolean;
+    }
+  | {
+      type: 'done';
+      step: number;
+      content: string;
+      done_reason?: string;
+      metrics?: GenerationMetrics;
+    };
+
 /**
  * Pull model progress response from streaming implementation
  */
@@ -21,6 +44,310 @@ export interface PullModelProgress {
   digest?: string;
   total?: number;
   completed?: number;
+  // Aggregate over all layers, present once a layer reported its size
+  overall_completed?: number;
+  overall_total?: number;
+  overall_percent?: number;
+  current_layer?: string;
+  current_layer_index?: number;
+  layer_count?: number;
+  bytes_per_second?: number;
+  eta_seconds?: number;
+  // Only on "retrying" events, after the stream was interrupted
+  attempt?: number;
+  max_attempts?: number;
+  retry_in_ms?: number;
+  error?: string;
+  // Only on "waiting_for_window" events, while the download window is closed
+  window_start?: string;
+  window_end?: string;
+  opens_in_seconds?: number;
+  // Only on "verified" and "verification_failed" events, after the pull finished
+  verification?: ModelVerification;
+}
+
+export type BlobStatus = 'ok' | 'missing' | 'size_mismatch' | 'corrupt';
+
+export interface BlobCheck {
+  digest: string;
+  media_type: string;
+  size: number;
+  status: BlobStatus;
+  actual_size: number | null;
+  actual_digest: string | null;
+}
+
+/**
+ * Blobs of a local model checked against its manifest
+ */
+export interface ModelVerification {
+  model: string;
+  manifest_path: string;
+  valid: boolean;
+  blobs: BlobCheck[];
+}
+
+export interface StoredModel {
+  name: string;
+  manifest_path: string;
+  blobs: string[];
+  size: number;
+  // Bytes freed when this model is deleted, shared blobs stay
+  unique_size: number;
+}
+
+export interface StoredBlob {
+  digest: string;
+  path: string;
+  size: number;
+  present: boolean;
+  referenced_by: string[];
+}
+
+/**
+ * Manifests and blobs physically stored in the models directory
+ */
+export interface StoreInventory {
+  models_dir: string;
+  models: StoredModel[];
+  blobs: StoredBlob[];
+  orphaned_blobs: StoredBlob[];
+  invalid_manifests: string[];
+  total_size: number;
+  shared_size: number;
+  orphaned_size: number;
+}
+
+/**
+ * Progress of importModelFile and transferModel: hashing and upload events name the local file,
+ * then the status lines of the create request follow
+ */
+export interface ImportModelProgress {
+  status: string;
+  file?: string;
+  digest?: string;
+  total?: number;
+  completed?: number;
+}
+
+export type GcReason = 'orphaned' | 'stale_partial';
+
+export interface GcCandidate {
+  path: string;
+  size: number;
+  reason: GcReason;
+}
+
+/**
+ * Result of a models directory cleanup, or of a dry run
+ */
+export interface GcReport {
+  dry_run: boolean;
+  candidates: GcCandidate[];
+  reclaimable_bytes: number;
+  removed: string[];
+  freed_bytes: number;
+  errors: string[];
+}
+
+export type ModelfileDirective =
+  | { directive: 'FROM'; model: string }
+  | { directive: 'PARAMETER'; name: string; value: string }
+  | { directive: 'TEMPLATE'; template: string }
+  | { directive: 'SYSTEM'; system: string }
+  | { directive: 'ADAPTER'; path: string }
+  | { directive: 'LICENSE'; license: string }
+  | { directive: 'MESSAGE'; role: 'system' | 'user' | 'assistant'; content: string }
+  | { directive: 'REQUIRES'; version: string };
+
+export type ModelfileEntry =
+  | ({ kind: 'directive'; line: number } & ModelfileDirective)
+  | { kind: 'comment'; text: string; line: number }
+  | { kind: 'blank'; raw: string; line: number }
+  | { kind: 'invalid'; raw: string; line: number };
+
+/**
+ * Parse or validation problem, `line` is 1-based
+ */
+export interface ModelfileDiagnostic {
+  line: number;
+  severity: 'error' | 'warning';
+  message: string;
+}
+
+export interface ModelfileAnalysis {
+  modelfile: { entries: ModelfileEntry[] };
+  diagnostics: ModelfileDiagnostic[];
+}
+
+/**
+ * Reachability of one host in a fan-out inventory
+ */
+export interface HostStatus {
+  profile: string | null;
+  host: string;
+  reachable: boolean;
+  error?: string;
+  elapsed_ms: number;
+  model_count: number;
+  running_count: number;
+}
+
+export interface ModelPlacement {
+  profile: string | null;
+  host: string;
+  digest: string;
+  size: number;
+  modified_at: string;
+  running: boolean;
+}
+
+/**
+ * A model name across hosts, drift means the hosts have different digests for it
+ */
+export interface InventoryModel {
+  name: string;
+  placements: ModelPlacement[];
+  digests: string[];
+  drift: boolean;
+}
+
+export interface HostInventory {
+  hosts: HostStatus[];
+  models: InventoryModel[];
+  drifted_models: number;
+}
+
+export type ChangeKind = 'added' | 'removed' | 'changed';
+
+export interface ParameterChange {
+  name: string;
+  kind: ChangeKind;
+  before: string[];
+  after: string[];
+}
+
+export interface InstructionChange {
+  directive: string;
+  kind: ChangeKind;
+  before: string[];
+  after: string[];
+}
+
+export interface TextDiff {
+  before: string;
+  after: string;
+  lines: { op: 'same' | 'added' | 'removed'; text: string }[];
+}
+
+export interface LayerRef {
+  digest: string;
+  media_type?: string;
+  size?: number;
+}
+
+/**
+ * Structural difference between two models, from base to other.
+ * Layers come from local manifests when from_manifests is set, otherwise
+ * only the blobs referenced by FROM and ADAPTER are compared.
+ */
+export interface ModelDiff {
+  base: string;
+  other: string;
+  identical: boolean;
+  parameters: ParameterChange[];
+  template?: TextDiff;
+  system?: TextDiff;
+  instructions: InstructionChange[];
+  details: { field: string; before: unknown; after: unknown }[];
+  layers: {
+    from_manifests: boolean;
+    shared: LayerRef[];
+    only_base: LayerRef[];
+    only_other: LayerRef[];
+  };
+}
+
+export type DownloadStatus = 'queued' | 'active' | 'paused' | 'failed' | 'completed';
+
+/**
+ * Entry of the backend download queue
+ */
+export interface DownloadQueueEntry {
+  channel_id: string;
+  model_name: string;
+  priority: number;
+  status: DownloadStatus;
+  enqueued_at: number;
+}
+
+/**
+ * Saved download progress as reported by check_download_status
+ */
+export interface DownloadProgressStatus {
+  model_name: string;
+  channel_id: string;
+  completed_bytes: number;
+  total_bytes: number;
+  last_updated: number;
+  status: DownloadStatus;
+  priority: number;
+  position: number;
+  enqueued_at: number;
+}
+
+/**
+ * Timings (nanoseconds) and token counts reported on the final stream event
+ */
+export interface GenerationMetrics {
+  total_duration?: number;
+  load_duration?: number;
+  prompt_eval_count?: number;
+  prompt_eval_duration?: number;
+  eval_count?: number;
+  eval_duration?: number;
+  prompt_tokens_per_second?: number;
+  tokens_per_second?: number;
+}
+
+/**
+ * Event emitted on the caller's channel by generate_completion_stream
+ */
+export interface GenerateStreamChunk extends GenerationMetrics {
+  content: string;
+  done: boolean;
+  done_reason?: string;
+  context?: number[];
+}
+
+/**
+ * Event emitted by generate_chat_completion while streaming a chat
+ */
+export interface ChatStreamChunk extends GenerationMetrics {
+  content: string;
+  done: boolean;
+  role?: string;
+  thinking?: string;
+  tool_calls?: any[];
+  done_reason?: string;
+}
+
+export interface SchemaError {
+  /** JSON pointer into the model output, empty for the root */
+  path: string;
+  message: string;
+}
+
+/**
+ * Result of generate_structured: the validated JSON or the validation report
+ */
+export interface StructuredResponse<T = any> {
+  valid: boolean;
+  value?: T;
+  raw: string;
+  attempts: number;
+  errors: SchemaError[];
+  metrics: GenerationMetrics;
 }
 
 export interface GenerateRequest {
@@ -63,15 +390,60 @@ export interface ModelInfo {
   capabilities: string[];
 }
 
+/**
+ * Structured error returned by the Rust Ollama commands
+ */
+export interface OllamaBackendError {
+  kind:
+    | 'connection_refused'
+    | 'timeout'
+    | 'interrupted'
+    | 'http'
+    | 'model_not_found'
+    | 'unauthorized'
+    | 'parse'
+    | 'cancelled'
+    | 'other';
+  message: string;
+  status: number | null;
+}
+
+export function isOllamaBackendError(error: unknown): error is OllamaBackendError {
+  return typeof error === 'object' && error !== null && 'kind' in error && 'message' in error;
+}
+
+/**
+ * Get a readable message from an error thrown by a Rust command
+ */
+export function formatBackendError(error: unknown): string {
+  return isOllamaBackendError(error) ? error.message : String(error);
+}
+
 class OllamaTauriAPI {
+  /**
+   * @param profile Server profile the requests go to, the active server when omitted
+   */
+  constructor(private readonly profile?: string) {}
+
+  /**
+   * API bound to a server profile, e.g. `ollamaTauriApi.forProfile('gpu-box').listModels()`
+   */
+  forProfile(profile: string): OllamaTauriAPI {
+    return new OllamaTauriAPI(profile);
+  }
+
   /**
    * Check Ollama service connection
    */
   async checkConnection(): Promise<boolean> {
     try {
-      const isConnected = await invoke<boolean>('check_connection');
+      const isConnected = await invoke<boolean>('check_connection', { profile: this.profile });
       return isConnected;
     } catch (error) {
+      // Rejected credentials need a different fix than a stopped server
+      if (isOllamaBackendError(error) && error.kind === 'unauthorized') {
+        throw error;
+      }
       console.error('Failed to check Ollama connection:', error);
       return false;
     }
@@ -82,7 +454,7 @@ class OllamaTauriAPI {
    */
   async getVersion(): Promise<OllamaVersion | null> {
     try {
-      const version = await invoke<OllamaVersion>('get_version');
+      const version = await invoke<OllamaVersion>('get_version', { profile: this.profile });
       return version;
     } catch (error) {
       console.error('Failed to get Ollama version:', error);
@@ -95,7 +467,7 @@ class OllamaTauriAPI {
    */
   async listModels(): Promise<OllamaModel[]> {
     try {
-      const models = await invoke<OllamaModel[]>('list_models');
+      const models = await invoke<OllamaModel[]>('list_models', { profile: this.profile });
       return models || [];
     } catch (error) {
       console.error('Failed to list Ollama models:', error);
@@ -108,7 +480,7 @@ class OllamaTauriAPI {
    */
   async listRunningModels(): Promise<OllamaRunningModel[]> {
     try {
-      const models = await invoke<OllamaRunningModel[]>('list_running_models');
+      const models = await invoke<OllamaRunningModel[]>('list_running_models', { profile: this.profile });
       return models || [];
     } catch (error) {
       console.error('Failed to list running Ollama models:', error);
@@ -123,7 +495,8 @@ class OllamaTauriAPI {
     try {
       const modelInfo = await invoke<ModelInfo>('show_model_info', { 
         modelName, 
-        verbose 
+        verbose,
+        profile: this.profile
       });
       return modelInfo;
     } catch (error) {
@@ -133,34 +506,69 @@ class OllamaTauriAPI {
   }
 
   /**
-   * Copy model
+   * Copy model, rejects with an OllamaBackendError carrying Ollama's message
    */
-  async copyModel(source: string, destination: string): Promise<boolean> {
-    try {
-      const success = await invoke<boolean>('copy_model', { 
-        source, 
-        destination 
-      });
-      return success;
-    } catch (error) {
-      console.error('Failed to copy model:', error);
-      return false;
-    }
+  async copyModel(source: string, destination: string): Promise<void> {
+    await invoke<void>('copy_model', { 
+      source, 
+      destination,
+      profile: this.profile
+    });
   }
 
   /**
-   * Delete model
+   * Re-hash the blobs of a local model and report missing or corrupt ones
    */
-  async deleteModel(modelName: string): Promise<boolean> {
-    try {
-      const success = await invoke<boolean>('delete_model', { 
-        modelName 
-      });
-      return success;
-    } catch (error) {
-      console.error('Failed to delete model:', error);
-      return false;
-    }
+  async verifyModel(modelName: string): Promise<ModelVerification> {
+    return await invoke<ModelVerification>('verify_model', { modelName, profile: this.profile });
+  }
+
+  /**
+   * Inspect the models directory on disk, including shared and orphaned blobs
+   */
+  async inspectModelStore(): Promise<StoreInventory> {
+    return await invoke<StoreInventory>('inspect_model_store', { profile: this.profile });
+  }
+
+  /**
+   * Find orphaned blobs and stale partial downloads in the models directory
+   * @param dryRun Only report what would be deleted
+   * @param confirmed Candidate paths from a dry run to delete, required when not a dry run
+   */
+  async gcModelsDir(dryRun: boolean, confirmed?: string[]): Promise<GcReport> {
+    return await invoke<GcReport>('gc_models_dir', { dryRun, confirmed, profile: this.profile });
+  }
+
+  /**
+   * Parse a Modelfile and check its instructions and parameters, for editor diagnostics
+   */
+  async parseModelfile(content: string): Promise<ModelfileAnalysis> {
+    return await invoke<ModelfileAnalysis>('parse_modelfile', { content });
+  }
+
+  /**
+   * Compare parameters, template, modelfile instructions, details and layers of two models
+   */
+  async diffModels(base: string, other: string): Promise<ModelDiff> {
+    return await invoke<ModelDiff>('diff_models', { base, other, profile: this.profile });
+  }
+
+  /**
+   * List installed and running models on every configured host at once
+   * @param timeoutSecs Time each host gets to answer, 10 seconds by default
+   */
+  async inventoryAllHosts(timeoutSecs?: number): Promise<HostInventory> {
+    return await invoke<HostInventory>('inventory_all_hosts', { timeoutSecs });
+  }
+
+  /**
+   * Delete model, a missing model rejects with kind 'model_not_found'
+   */
+  async deleteModel(modelName: string): Promise<void> {
+    await invoke<void>('delete_model', { 
+      modelName,
+      profile: this.profile
+    });
   }
 
   /**
@@ -168,9 +576,10 @@ class OllamaTauriAPI {
    * @param modelName The name of the model to pull
    * @param onProgress Optional callback to receive progress updates
    * @param signal Optional AbortSignal to cancel the download
+   * @param priority Queue priority, higher priorities start first
    * @returns Promise that resolves when the model is fully downloaded
    */
-  async pullModel(modelName: string, onProgress?: (progress: PullModelProgress) => void, signal?: AbortSignal): Promise<string> {
+  async pullModel(modelName: string, onProgress?: (progress: PullModelProgress) => void, signal?: AbortSignal, priority?: number): Promise<string> {
     try {
       // Create a deterministic channelId to ensure the same model uses the same ID for each download
   // This is crucial for pausing and resuming downloads
@@ -232,7 +641,9 @@ class OllamaTauriAPI {
         
         const result = await invoke<string>('pull_model', { 
           modelName: modelName,
-          channelId: uniqueChannelId
+          channelId: uniqueChannelId,
+          priority,
+          profile: this.profile
         });
         
         return result;
@@ -248,30 +659,143 @@ class OllamaTauriAPI {
       }
     } catch (error: any) {
       // Special handling for user-initiated abort
-      if (error.toString().includes('aborted') || signal?.aborted) {
+      if (formatBackendError(error).includes('aborted') || signal?.aborted) {
         console.log(`Download of ${modelName} was aborted by user`);
         throw new Error('Download aborted by user');
       }
       
       // Standard error handling
       console.error('Failed to pull model:', error);
-      throw new Error(`Failed to pull model: ${error}`);
+      throw new Error(`Failed to pull model: ${formatBackendError(error)}`);
     }
   }
 
   /**
-   * Push model - non-streaming implementation
+   * Pause a queued or running pull, its progress is kept for resumePull
    */
-  async pushModel(modelName: string, insecure: boolean = false): Promise<string> {
+  async pausePull(modelName: string): Promise<boolean> {
     try {
-      const result = await invoke<string>('push_model', { 
+      const channelId = `model-pull-${modelName.replace(/[^a-zA-Z0-9]/g, '_')}`;
+      return await invoke<boolean>('pause_pull', { channelId });
+    } catch (error) {
+      console.error('Failed to pause pull:', error);
+      return false;
+    }
+  }
+
+  /**
+   * Resume a paused or failed pull, resolves when the download finishes
+   */
+  async resumePull(modelName: string): Promise<string> {
+    try {
+      const channelId = `model-pull-${modelName.replace(/[^a-zA-Z0-9]/g, '_')}`;
+      return await invoke<string>('resume_pull', { modelName, channelId, profile: this.profile });
+    } catch (error) {
+      console.error('Failed to resume pull:', error);
+      throw new Error(`Failed to resume pull: ${formatBackendError(error)}`);
+    }
+  }
+
+  /**
+   * Status and byte counts of a pull, null if the model was never queued
+   */
+  async checkDownloadStatus(modelName: string): Promise<DownloadProgressStatus | null> {
+    try {
+      const channelId = `model-pull-${modelName.replace(/[^a-zA-Z0-9]/g, '_')}`;
+      return await invoke<DownloadProgressStatus | null>('check_download_status', { channelId });
+    } catch (error) {
+      console.error('Failed to check download status:', error);
+      return null;
+    }
+  }
+
+  /**
+   * Current download queue, active downloads first
+   */
+  async getDownloadQueue(): Promise<DownloadQueueEntry[]> {
+    try {
+      return await invoke<DownloadQueueEntry[]>('get_download_queue');
+    } catch (error) {
+      console.error('Failed to get download queue:', error);
+      throw new Error(`Failed to get download queue: ${formatBackendError(error)}`);
+    }
+  }
+
+  /**
+   * Listen for download queue changes
+   * @returns Function that removes the listener
+   */
+  async onDownloadQueueChanged(callback: (queue: DownloadQueueEntry[]) => void): Promise<() => void> {
+    const { listen } = await import('@tauri-apps/api/event');
+    return listen<DownloadQueueEntry[]>('download-queue-changed', (event) => {
+      callback(event.payload);
+    });
+  }
+
+  /**
+   * Move a queued download to a new position (0 starts next)
+   */
+  async moveDownload(channelId: string, position: number): Promise<boolean> {
+    try {
+      return await invoke<boolean>('move_download', { channelId, position });
+    } catch (error) {
+      console.error('Failed to move download:', error);
+      return false;
+    }
+  }
+
+  /**
+   * Change the priority of a queued download
+   */
+  async setDownloadPriority(channelId: string, priority: number): Promise<boolean> {
+    try {
+      return await invoke<boolean>('set_download_priority', { channelId, priority });
+    } catch (error) {
+      console.error('Failed to set download priority:', error);
+      return false;
+    }
+  }
+
+  /**
+   * Push model, streaming per-layer upload progress
+   * @param modelName Model to push, including its registry namespace
+   * @param insecure Allow insecure connections to the registry
+   * @param onProgress Callback receiving the push status lines
+   * @param signal Optional AbortSignal to stop the push
+   */
+  async pushModel(
+    modelName: string,
+    insecure: boolean = false,
+    onProgress?: (progress: PullModelProgress) => void,
+    signal?: AbortSignal
+  ): Promise<string> {
+    const channelId = `push-${Date.now()}-${Math.random().toString(36).slice(2, 10)}`;
+    const { listen } = await import('@tauri-apps/api/event');
+    const unlisten = await listen<PullModelProgress>(channelId, (event) => {
+      if (event.payload) {
+        onProgress?.(event.payload);
+      }
+    });
+
+    // The channel id is also the request id used to cancel the push
+    const abortListener = () => {
+      void this.cancelGeneration(channelId);
+    };
+    signal?.addEventListener('abort', abortListener);
+
+    try {
+      return await invoke<string>('push_model', {
         modelName,
-        insecure 
+        insecure,
+        channelId,
+        profile: this.profile
       });
-      return result;
     } catch (error) {
       console.error('Failed to push model:', error);
-      throw new Error(`Failed to push model: ${error}`);
+      throw new Error(`Failed to push model: ${formatBackendError(error)}`);
+    } finally {
+      unlisten();
+      signal?.removeEventListener('abort', abortListener);
     }
   }
 
@@ -302,12 +826,134 @@ class OllamaTauriAPI {
   async generateCompletion(request: GenerateRequest): Promise<string> {
     try {
       const response = await invoke<string>('generate_completion', { 
-        request 
+        request,
+        profile: this.profile
       });
       return response;
     } catch (error) {
       console.error('Failed to generate completion:', error);
-      throw new Error(`Failed to generate completion: ${error}`);
+      throw new Error(`Failed to generate completion: ${formatBackendError(error)}`);
+    }
+  }
+
+  /**
+   * Generate text completion with streaming tokens
+   * @param request The generate request
+   * @param onChunk Callback receiving token deltas and the final stats event
+   * @param signal Optional AbortSignal to stop the generation
+   * @returns Promise that resolves to the full completion text
+   */
+  async generateCompletionStream(
+    request: GenerateRequest,
+    onChunk: (chunk: GenerateStreamChunk) => void,
+    signal?: AbortSignal
+  ): Promise<string> {
+    // Each call gets its own channel so concurrent completions don't mix tokens
+    const channelId = `generate-${Date.now()}-${Math.random().toString(36).slice(2, 10)}`;
+    const { listen } = await import('@tauri-apps/api/event');
+    const unlisten = await listen<GenerateStreamChunk>(channelId, (event) => {
+      if (event.payload) {
+        onChunk(event.payload);
+      }
+    });
+
+    // The channel id is also the request id used to cancel the stream
+    const abortListener = () => {
+      void this.cancelGeneration(channelId);
+    };
+    signal?.addEventListener('abort', abortListener);
+
+    try {
+      return await invoke<string>('generate_completion_stream', {
+        request,
+        channelId,
+        profile: this.profile
+      });
+    } catch (error) {
+      console.error('Failed to stream completion:', error);
+      throw new Error(`Failed to generate completion: ${formatBackendError(error)}`);
+    } finally {
+      unlisten();
+      signal?.removeEventListener('abort', abortListener);
+    }
+  }
+
+  /**
+   * Chat with built-in backend tools (read_file, get_system_info, list_models)
+   * @param request The chat request without tools, the backend fills in its built-in ones
+   * @param onEvent Callback receiving one event per loop step
+   * @param allowedDirs Directories the read_file tool may read from
+   * @param signal Optional AbortSignal to stop the loop
+   * @returns Promise that resolves to the final assistant message
+   */
+  async chatWithTools(
+    request: ChatRequest,
+    onEvent: (event: ToolChatEvent) => void,
+    allowedDirs: string[] = [],
+    signal?: AbortSignal
+  ): Promise<ChatMessage> {
+    const channelId = `tool-chat-${Date.now()}-${Math.random().toString(36).slice(2, 10)}`;
+    const { listen } = await import('@tauri-apps/api/event');
+    const unlisten = await listen<ToolChatEvent>(channelId, (event) => {
+      if (event.payload) {
+        onEvent(event.payload);
+      }
+    });
+
+    const abortListener = () => {
+      void this.cancelGeneration(channelId);
+    };
+    signal?.addEventListener('abort', abortListener);
+
+    try {
+      return await invoke<ChatMessage>('chat_with_tools', {
+        request,
+        channelId,
+        allowedDirs,
+        profile: this.profile
+      });
+    } catch (error) {
+      console.error('Failed to chat with tools:', error);
+      throw new Error(`Failed to chat with tools: ${formatBackendError(error)}`);
+    } finally {
+      unlisten();
+      signal?.removeEventListener('abort', abortListener);
+    }
+  }
+
+  /**
+   * Stop an in-flight chat or generate stream
+   * @param requestId The request id (channel id) the stream was started with
+   * @returns Promise that resolves to true if a running stream was cancelled
+   */
+  async cancelGeneration(requestId: string): Promise<boolean> {
+    try {
+      return await invoke<boolean>('cancel_generation', { requestId });
+    } catch (error) {
+      console.error('Failed to cancel generation:', error);
+      return false;
+    }
+  }
+
+  /**
+   * Generate JSON constrained and validated by a JSON Schema,
+   * retrying up to maxRetries times with the validation errors fed back
+   */
+  async generateStructured<T = any>(
+    request: ChatRequest,
+    schema: object,
+    maxRetries?: number
+  ): Promise<StructuredResponse<T>> {
+    try {
+      return await invoke<StructuredResponse<T>>('generate_structured', {
+        request,
+        schema,
+        maxRetries,
+        profile: this.profile
+      });
+    } catch (error) {
+      console.error('Failed to generate structured output:', error);
+      throw new Error(`Failed to generate structured output: ${formatBackendError(error)}`);
     }
   }
 
@@ -317,12 +963,13 @@ class OllamaTauriAPI {
   async generateChat(request: ChatRequest): Promise<ChatMessage> {
     try {
       const response = await invoke<ChatMessage>('generate_chat', { 
-        request 
+        request,
+        profile: this.profile
       });
       return response;
     } catch (error) {
       console.error('Failed to generate chat:', error);
-      throw new Error(`Failed to generate chat: ${error}`);
+      throw new Error(`Failed to generate chat: ${formatBackendError(error)}`);
     }
   }
 
@@ -343,34 +990,153 @@ class OllamaTauriAPI {
         { 
           model,
           input: inputValue,
-          options
+          options,
+          profile: this.profile
         }
       );
       return response;
     } catch (error) {
       console.error('Failed to generate embeddings:', error);
-      throw new Error(`Failed to generate embeddings: ${error}`);
+      throw new Error(`Failed to generate embeddings: ${formatBackendError(error)}`);
     }
   }
 
   /**
-   * Create model
+   * Create model, streaming its status lines (e.g. quantization progress)
+   * @param modelName Name of the new model
+   * @param from Optional existing model to create from
+   * @param modelfile Optional modelfile content
+   * @param onProgress Callback receiving the create status lines
+   * @param signal Optional AbortSignal to stop the create
    */
   async createModel(
     modelName: string,
     from?: string,
-    modelfile?: string
+    modelfile?: string,
+    onProgress?: (progress: PullModelProgress) => void,
+    signal?: AbortSignal
   ): Promise<string> {
+    const channelId = `create-${Date.now()}-${Math.random().toString(36).slice(2, 10)}`;
+    const { listen } = await import('@tauri-apps/api/event');
+    const unlisten = await listen<PullModelProgress>(channelId, (event) => {
+      if (event.payload) {
+        onProgress?.(event.payload);
+      }
+    });
+
+    // The channel id is also the request id used to cancel the create
+    const abortListener = () => {
+      void this.cancelGeneration(channelId);
+    };
+    signal?.addEventListener('abort', abortListener);
+
     try {
-      const result = await invoke<string>('create_model', {
+      return await invoke<string>('create_model', {
         modelName,
         from,
-        modelfile
+        modelfile,
+        channelId,
+        profile: this.profile
       });
-      return result;
     } catch (error) {
       console.error('Failed to create model:', error);
-      throw new Error(`Failed to create model: ${error}`);
+      throw new Error(`Failed to create model: ${formatBackendError(error)}`);
+    } finally {
+      unlisten();
+      signal?.removeEventListener('abort', abortListener);
+    }
+  }
+
+  /**
+   * Create a model from a local .gguf file or safetensors directory
+   * @param modelName Name of the new model
+   * @param path Path of the .gguf file or the safetensors directory
+   * @param onProgress Callback receiving hashing, upload and create progress
+   * @param quantize Optional quantization type, e.g. q4_K_M
+   * @param signal Optional AbortSignal to stop the import
+   */
+  async importModelFile(
+    modelName: string,
+    path: string,
+    onProgress?: (progress: ImportModelProgress) => void,
+    quantize?: string,
+    signal?: AbortSignal
+  ): Promise<string> {
+    const channelId = `import-${Date.now()}-${Math.random().toString(36).slice(2, 10)}`;
+    const { listen } = await import('@tauri-apps/api/event');
+    const unlisten = await listen<ImportModelProgress>(channelId, (event) => {
+      if (event.payload) {
+        onProgress?.(event.payload);
+      }
+    });
+
+    // The channel id is also the request id used to cancel the import
+    const abortListener = () => {
+      void this.cancelGeneration(channelId);
+    };
+    signal?.addEventListener('abort', abortListener);
+
+    try {
+      return await invoke<string>('import_model_file', {
+        modelName,
+        path,
+        channelId,
+        quantize,
+        profile: this.profile
+      });
+    } catch (error) {
+      console.error('Failed to import model:', error);
+      throw new Error(`Failed to import model: ${formatBackendError(error)}`);
+    } finally {
+      unlisten();
+      signal?.removeEventListener('abort', abortListener);
+    }
+  }
+
+  /**
+   * Copy a model from this API's server to another one
+   * The source models directory has to be reachable from this machine.
+   * @param modelName Model on the source server
+   * @param targetProfile Server profile to copy to, the active server when omitted
+   * @param onProgress Callback receiving hashing, upload and create progress
+   * @param targetName Name on the target, defaults to modelName
+   * @param signal Optional AbortSignal to stop the transfer
+   */
+  async transferModel(
+    modelName: string,
+    targetProfile?: string,
+    onProgress?: (progress: ImportModelProgress) => void,
+    targetName?: string,
+    signal?: AbortSignal
+  ): Promise<string> {
+    const channelId = `transfer-${Date.now()}-${Math.random().toString(36).slice(2, 10)}`;
+    const { listen } = await import('@tauri-apps/api/event');
+    const unlisten = await listen<ImportModelProgress>(channelId, (event) => {
+      if (event.payload) {
+        onProgress?.(event.payload);
+      }
+    });
+
+    // The channel id is also the request id used to cancel the transfer
+    const abortListener = () => {
+      void this.cancelGeneration(channelId);
+    };
+    signal?.addEventListener('abort', abortListener);
+
+    try {
+      return await invoke<string>('transfer_model', {
+        modelName,
+        sourceProfile: this.profile,
+        targetProfile,
+        targetName,
+        channelId
+      });
+    } catch (error) {
+      console.error('Failed to transfer model:', error);
+      throw new Error(`Failed to transfer model: ${formatBackendError(error)}`);
+    } finally {
+      unlisten();
+      signal?.removeEventListener('abort', abortListener);
     }
   }
 
@@ -380,7 +1146,8 @@ class OllamaTauriAPI {
   async loadModel(modelName: string): Promise<boolean> {
     try {
       const success = await invoke<boolean>('load_model', { 
-        modelName 
+        modelName,
+        profile: this.profile
       });
       return success;
     } catch (error) {
@@ -395,7 +1162,8 @@ class OllamaTauriAPI {
   async unloadModel(modelName: string): Promise<boolean> {
     try {
       const success = await invoke<boolean>('unload_model', { 
-        modelName 
+        modelName,
+        profile: this.profile
       });
       return success;
     } catch (error) {
@@ -427,7 +1195,7 @@ class OllamaTauriAPI {
       return result;
     } catch (error) {
       console.error('Failed to restart Ollama:', error);
-      throw new Error(`Failed to restart Ollama: ${error}`);
+      throw new Error(`Failed to restart Ollama: ${formatBackendError(error)}`);
     }
   }
 }
@@ -157,13 +157,42 @@ export const useDownloadStore = create<DownloadState>()(
                     return;
                   }
                   
+                  // The backend reissues the pull after an interruption, keep showing the download
+                  if (progress.status === 'retrying') {
+                    console.warn(`Download of ${modelName} interrupted, retry ${progress.attempt}/${progress.max_attempts} in ${progress.retry_in_ms}ms: ${progress.error}`);
+                  }
+                  
+                  // Queued until the configured download window opens
+                  if (progress.status === 'waiting_for_window') {
+                    console.info(`Download of ${modelName} waits for the download window ${progress.window_start}-${progress.window_end}, opens in ${progress.opens_in_seconds}s`);
+                    return;
+                  }
+                  
+                  // The backend checks the blobs against the manifest once the pull succeeded
+                  if (progress.status === 'verifying' || progress.status === 'verified' || progress.status === 'verification_failed') {
+                    if (progress.verification && !progress.verification.valid) {
+                      console.error(`Verification of ${modelName} failed:`, progress.verification.blobs.filter(b => b.status !== 'ok'));
+                    }
+                    updateTaskState(modelName, { status: 'finalizing' });
+                    return;
+                  }
+                  
                   // Prepare update object
                   const updates: Partial<DownloadTask> = {
                     status: progress.status === 'success' ? 'finalizing' : 'downloading',
                   };
                   
-                  // Only calculate progress when we have valid total and completed amounts
-                  if (progress.total && progress.completed && progress.total > 0) {
+                  // Prefer the backend's aggregate over all layers, per-layer counters reset on every new layer
+                  if (progress.overall_total && progress.overall_total > 0) {
+                    Object.assign(updates, {
+                      total: progress.overall_total,
+                      completed: progress.overall_completed,
+                      progress: progress.overall_percent,
+                      speed: progress.bytes_per_second,
+                      remainingTime: progress.eta_seconds ?? Infinity
+                    });
+                  } else if (progress.total && progress.completed && progress.total > 0) {
+                    // Only calculate progress when we have valid total and completed amounts
                     // Add data point for speed calculation
                     speedDataPoints.push({
                       timestamp: now,